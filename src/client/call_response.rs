@@ -88,6 +88,39 @@ impl CallResponse {
     }
 }
 
+/// Tuples, returned from `call16` request.
+///
+/// Unlike [`CallResponse`], each value, returned from function, is wrapped into tuple:
+/// scalars become tuples with single element, tables are converted to tuples
+/// and tuples are returned as is.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Call16Response(pub(crate) rmpv::Value);
+
+impl Call16Response {
+    /// Decode first returned tuple, dropping everything else.
+    ///
+    /// For function, returning single scalar value, `T` should be single-element tuple.
+    pub fn decode_first<T>(self) -> Result<T, DecodingError>
+    where
+        T: DeserializeOwned,
+    {
+        let first = CallResponse(self.0)
+            .into_data_tuple()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| DecodingError::invalid_tuple_length(1, 0))?;
+        Ok(rmpv::ext::from_value(first)?)
+    }
+
+    /// Decode all returned tuples into type.
+    pub fn decode_full<T>(self) -> Result<T, DecodingError>
+    where
+        T: DeserializeOwned,
+    {
+        CallResponse(self.0).decode_full()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
@@ -162,4 +195,28 @@ mod tests {
         let resp = build_tuple_response(vec![Value::Boolean(true), Value::Boolean(false)]);
         assert_matches!(CallResponse(resp).decode_full(), Ok((true, Some(false))));
     }
+
+    #[test]
+    fn call16_decode_first() {
+        let resp = build_tuple_response(vec![
+            Value::Array(vec![Value::Boolean(true)]),
+            Value::Array(vec![Value::Boolean(false)]),
+        ]);
+        assert_matches!(Call16Response(resp).decode_first(), Ok((true,)));
+
+        let resp = build_tuple_response(vec![]);
+        assert_matches!(Call16Response(resp).decode_first::<(bool,)>(), Err(_));
+    }
+
+    #[test]
+    fn call16_decode_full() {
+        let resp = build_tuple_response(vec![
+            Value::Array(vec![Value::Boolean(true)]),
+            Value::Array(vec![Value::Boolean(false)]),
+        ]);
+        assert_matches!(
+            Call16Response(resp).decode_full::<Vec<(bool,)>>(),
+            Ok(x) if x == vec![(true,), (false,)]
+        );
+    }
 }
@@ -1,5 +1,7 @@
 use std::{
+    collections::HashSet,
     fmt,
+    net::SocketAddr,
     num::NonZeroUsize,
     sync::{
         atomic::{AtomicU32, Ordering},
@@ -8,24 +10,28 @@ use std::{
     time::Duration,
 };
 
+use anyhow::anyhow;
 use async_trait::async_trait;
-use futures::TryFutureExt;
+use futures::{future::join_all, TryFutureExt};
 use lru::LruCache;
 use parking_lot::Mutex;
 use rmpv::Value;
-use tokio::time::timeout;
+use tokio::{
+    sync::{mpsc, watch},
+    time::{timeout_at, Instant},
+};
 use tracing::{debug, trace};
 
 use crate::{
     builder::ConnectionBuilder,
-    client::{Executor, Stream, Transaction, TransactionBuilder},
+    client::{Executor, Retrier, Stream, Transaction, TransactionBuilder, Watcher},
     codec::{
-        consts::TransactionIsolationLevel,
-        request::{EncodedRequest, Id, Request},
+        consts::{RequestType, TransactionIsolationLevel},
+        request::{EncodedRequest, Request, Rollback},
         response::ResponseBody,
     },
     transport::DispatcherSender,
-    ExecutorExt, Result,
+    ConnectionState, Error, ExecutorExt, ProtocolFeature, Result, ServerInfo, Spawner,
 };
 
 /// Connection to Tarantool instance.
@@ -33,8 +39,8 @@ use crate::{
 /// This type doesn't represent single TCP connection, but rather an abstraction
 /// for interaction with Tarantool instance.
 ///
-/// Underling implemenation could reconnect automatically (depending on builder configuration),
-/// and could do pooling in the future (not yet implemented!).
+/// Underling implemenation could reconnect automatically (depending on builder configuration).
+/// For pooling multiple connections check [`Pool`](crate::Pool).
 #[derive(Clone)]
 pub struct Connection {
     inner: Arc<ConnectionInner>,
@@ -44,14 +50,17 @@ struct ConnectionInner {
     dispatcher_sender: DispatcherSender,
     // TODO: change how stream id assigned when dispatcher have more than one connection
     next_stream_id: AtomicU32,
+    /// Streams of transactions, which are not yet committed or rolled back.
+    open_transactions: Mutex<HashSet<u32>>,
     timeout: Option<Duration>,
     transaction_timeout_secs: Option<f64>,
     transaction_isolation_level: TransactionIsolationLevel,
-    async_rt_handle: tokio::runtime::Handle,
+    spawner: Arc<dyn Spawner>,
     // TODO: tests
     // TODO: move sql statement cache to separate type
     sql_statement_cache: Option<Mutex<LruCache<String, u64>>>,
     sql_statement_cache_update_lock: Mutex<()>,
+    retrier: Option<Arc<Retrier>>,
 }
 
 impl Connection {
@@ -66,37 +75,38 @@ impl Connection {
         transaction_timeout: Option<Duration>,
         transaction_isolation_level: TransactionIsolationLevel,
         sql_statement_cache_capacity: usize,
+        retrier: Option<Arc<Retrier>>,
+        spawner: Arc<dyn Spawner>,
     ) -> Self {
         Self {
             inner: Arc::new(ConnectionInner {
                 dispatcher_sender,
                 // TODO: check if 0 is valid value
                 next_stream_id: AtomicU32::new(1),
+                open_transactions: Mutex::new(HashSet::new()),
                 timeout,
                 transaction_timeout_secs: transaction_timeout.as_ref().map(Duration::as_secs_f64),
                 transaction_isolation_level,
-                // NOTE: Safety: this method can be called only in async tokio context (because it
-                // is called only from ConnectionBuilder).
-                async_rt_handle: tokio::runtime::Handle::current(),
+                spawner,
                 sql_statement_cache: NonZeroUsize::new(sql_statement_cache_capacity)
                     .map(|x| Mutex::new(LruCache::new(x))),
                 sql_statement_cache_update_lock: Mutex::new(()),
+                retrier,
             }),
         }
     }
 
     /// Synchronously send request to channel and drop response.
-    #[allow(clippy::let_underscore_future)]
     pub(crate) fn send_request_sync_and_forget(&self, body: impl Request, stream_id: Option<u32>) {
         let this = self.clone();
         let req = EncodedRequest::new(body, stream_id);
-        let _ = self.inner.async_rt_handle.spawn(async move {
+        self.inner.spawner.spawn(Box::pin(async move {
             let res = futures::future::ready(req)
                 .err_into()
                 .and_then(|x| this.send_encoded_request(x))
                 .await;
             debug!("Response for background request: {:?}", res);
-        });
+        }));
     }
 
     // TODO: maybe other Ordering??
@@ -109,14 +119,131 @@ impl Connection {
         }
     }
 
-    // TODO: return response from server
-    /// Send ID request ([docs](https://www.tarantool.io/en/doc/latest/dev_guide/internals/box_protocol/#iproto-id-0x49)).
-    pub(crate) async fn id(&self, features: Id) -> Result<()> {
-        self.send_request(features).await.map(drop)
+    pub(crate) fn register_transaction(&self, stream_id: u32) {
+        let _ = self.inner.open_transactions.lock().insert(stream_id);
+    }
+
+    pub(crate) fn unregister_transaction(&self, stream_id: u32) {
+        let _ = self.inner.open_transactions.lock().remove(&stream_id);
+    }
+
+    /// Close connection.
+    ///
+    /// New requests are not accepted, open transactions are rolled back and
+    /// in-flight requests are awaited no longer than `timeout`. After that
+    /// connection and all background tasks are stopped. All clones of
+    /// this connection are closed as well.
+    ///
+    /// If some in-flight requests were not finished in time, they fail with
+    /// `ConnectionClosed` error and [`Error::CloseTimeout`] with number of
+    /// abandoned requests is returned.
+    pub async fn close(&self, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+
+        let open_transactions: Vec<_> = self.inner.open_transactions.lock().drain().collect();
+        let rollbacks = open_transactions.into_iter().map(|stream_id| async move {
+            debug!(
+                "Rolling back tranasction on stream {} (on close)",
+                stream_id
+            );
+            let res =
+                futures::future::ready(EncodedRequest::new(Rollback::default(), Some(stream_id)))
+                    .err_into()
+                    .and_then(|x| self.send_encoded_request(x))
+                    .await;
+            if let Err(err) = res {
+                debug!(
+                    "Failed to rollback transaction on stream {}: {:#}",
+                    stream_id, err
+                );
+            }
+        });
+        if timeout_at(deadline, join_all(rollbacks)).await.is_err() {
+            debug!("Timeout while rolling back transactions on close");
+        }
+
+        let abandoned = self
+            .inner
+            .dispatcher_sender
+            .close(deadline.saturating_duration_since(Instant::now()))
+            .await;
+        if abandoned == 0 {
+            Ok(())
+        } else {
+            Err(Error::CloseTimeout { abandoned })
+        }
+    }
+
+    /// Watch for changes of the key ([docs](https://www.tarantool.io/en/doc/latest/reference/reference_lua/box_events/)).
+    ///
+    /// Returned [`Watcher`] is a stream of values of the key, broadcasted with `box.broadcast`.
+    ///
+    /// Return [`Error::UnsupportedFeature`] if server does not support watchers.
+    pub fn watch(&self, key: impl Into<String>) -> Result<Watcher> {
+        self.require_feature(ProtocolFeature::Watchers)?;
+        let (subscription, rx) = self.inner.dispatcher_sender.watch(key.into());
+        Ok(Watcher::new(subscription, rx))
+    }
+
+    /// Information about server (version, supported features, etc.), received
+    /// when connection was established.
+    ///
+    /// Updated on each reconnect.
+    pub fn server_info(&self) -> Option<Arc<ServerInfo>> {
+        self.inner.dispatcher_sender.server_info()
+    }
+
+    /// Return error if server did not advertise `feature`.
+    ///
+    /// Nothing is checked if connection was not established yet.
+    pub(crate) fn require_feature(&self, feature: ProtocolFeature) -> Result<()> {
+        match self.inner.dispatcher_sender.server_info() {
+            Some(x) => x.require(feature),
+            None => Ok(()),
+        }
+    }
+
+    /// Check that server supports all features, required by request.
+    fn check_request_features(&self, request: &EncodedRequest) -> Result<()> {
+        if request.stream_id.is_some() {
+            self.require_feature(ProtocolFeature::Streams)?;
+        }
+        if matches!(
+            request.request_type,
+            RequestType::Begin | RequestType::Commit | RequestType::Rollback
+        ) {
+            self.require_feature(ProtocolFeature::Transactions)?;
+        }
+        Ok(())
+    }
+
+    /// Current state of connection.
+    pub fn state(&self) -> ConnectionState {
+        self.inner.dispatcher_sender.state()
     }
 
-    pub(crate) fn stream(&self) -> Stream {
-        Stream::new(self.clone())
+    /// Address of currently connected server.
+    ///
+    /// `None` if connection is not established at the moment or established
+    /// over unix socket. When connected through proxy, address of proxy is returned.
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        match self.inner.dispatcher_sender.state() {
+            ConnectionState::Connected { peer_addr, .. } => peer_addr,
+            _ => None,
+        }
+    }
+
+    /// Receiver, which is notified each time state of connection changes.
+    ///
+    /// Only latest state is kept, so intermediate states can be skipped
+    /// by slow receiver.
+    pub fn state_changes(&self) -> watch::Receiver<ConnectionState> {
+        self.inner.dispatcher_sender.state_changes()
+    }
+
+    pub(crate) fn stream(&self) -> Result<Stream> {
+        self.require_feature(ProtocolFeature::Streams)?;
+        Ok(Stream::new(self.clone()))
     }
 
     /// Create transaction, overriding default connection's parameters.
@@ -133,6 +260,72 @@ impl Connection {
         self.transaction_builder().begin().await
     }
 
+    async fn send_encoded_request_inner(
+        &self,
+        request: EncodedRequest,
+        push_tx: Option<mpsc::UnboundedSender<Value>>,
+    ) -> Result<Value> {
+        self.check_request_features(&request)?;
+
+        let retrier = self
+            .inner
+            .retrier
+            .as_ref()
+            .filter(|x| push_tx.is_none() && x.policy().is_retryable_request(&request));
+        let Some(retrier) = retrier else {
+            return self.send_encoded_request_once(request, push_tx).await;
+        };
+        let retry_policy = retrier.policy();
+
+        let mut retry = 0;
+        loop {
+            match self.send_encoded_request_once(request.clone(), None).await {
+                Ok(resp) => {
+                    retrier.deposit();
+                    return Ok(resp);
+                }
+                Err(err)
+                    if retry < retry_policy.max_retries()
+                        && retry_policy.is_retryable_error(&err) =>
+                {
+                    if !retrier.try_withdraw() {
+                        debug!("Request failed with error '{err}', retry budget is exhausted");
+                        return Err(err);
+                    }
+                    let interval = retry_policy.backoff_interval(retry);
+                    debug!("Request failed with error '{err}', retrying in {interval:?}");
+                    tokio::time::sleep(interval).await;
+                    retry += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    async fn send_encoded_request_once(
+        &self,
+        request: EncodedRequest,
+        push_tx: Option<mpsc::UnboundedSender<Value>>,
+    ) -> Result<Value> {
+        let sender = &self.inner.dispatcher_sender;
+        let resp = match push_tx {
+            Some(push_tx) => {
+                sender
+                    .send_with_push(request, push_tx, self.inner.timeout)
+                    .await?
+            }
+            None => sender.send(request, self.inner.timeout).await?,
+        };
+        match resp.body {
+            ResponseBody::Ok(x) => Ok(x),
+            ResponseBody::Error(x) => Err(x.into()),
+            // Out-of-band responses are passed to client separately
+            ResponseBody::Chunk(_) => Err(Error::Other(anyhow!(
+                "Out-of-band response received as final response"
+            ))),
+        }
+    }
+
     /// Get prepared statement id from cache (if it is enabled).
     ///
     /// If statement not present in cache, then prepare statement and put it
@@ -177,18 +370,19 @@ impl Connection {
 #[async_trait]
 impl Executor for Connection {
     async fn send_encoded_request(&self, request: EncodedRequest) -> Result<Value> {
-        let fut = self.inner.dispatcher_sender.send(request);
-        let resp = match self.inner.timeout {
-            Some(x) => timeout(x, fut).await??,
-            None => fut.await?,
-        };
-        match resp.body {
-            ResponseBody::Ok(x) => Ok(x),
-            ResponseBody::Error(x) => Err(x.into()),
-        }
+        self.send_encoded_request_inner(request, None).await
     }
 
-    fn stream(&self) -> Stream {
+    async fn send_encoded_request_with_push(
+        &self,
+        request: EncodedRequest,
+        push_tx: mpsc::UnboundedSender<Value>,
+    ) -> Result<Value> {
+        self.send_encoded_request_inner(request, Some(push_tx))
+            .await
+    }
+
+    fn stream(&self) -> Result<Stream> {
         self.stream()
     }
 
@@ -203,6 +397,10 @@ impl Executor for Connection {
     async fn get_cached_sql_statement_id(&self, statement: &str) -> Option<u64> {
         self.get_cached_sql_statement_id_inner(statement).await
     }
+
+    fn pinned_executor(&self) -> Option<Connection> {
+        None
+    }
 }
 
 impl fmt::Debug for Connection {
@@ -2,9 +2,10 @@ use std::fmt::Debug;
 
 use async_trait::async_trait;
 use rmpv::Value;
+use tokio::sync::mpsc;
 
 use crate::{
-    client::{private::Sealed, Stream, Transaction, TransactionBuilder},
+    client::{private::Sealed, Connection, Stream, Transaction, TransactionBuilder},
     codec::request::EncodedRequest,
     Result,
 };
@@ -15,10 +16,22 @@ pub trait Executor: Sealed + Send + Sync + Debug {
     /// Send encoded request.
     async fn send_encoded_request(&self, request: EncodedRequest) -> Result<Value>;
 
+    /// Send encoded request, passing all out-of-band responses (`box.session.push`)
+    /// into `push_tx`.
+    #[doc(hidden)]
+    async fn send_encoded_request_with_push(
+        &self,
+        request: EncodedRequest,
+        push_tx: mpsc::UnboundedSender<Value>,
+    ) -> Result<Value>;
+
     /// Get new [`Stream`].
     ///
     /// It is safe to create `Stream` from any type, implementing current trait.
-    fn stream(&self) -> Stream;
+    ///
+    /// Return [`Error::UnsupportedFeature`](crate::Error::UnsupportedFeature) if streams
+    /// are not enabled for connection.
+    fn stream(&self) -> Result<Stream>;
 
     /// Prepare [`TransactionBuilder`], which can be used to override parameters and create
     /// [`Transaction`].
@@ -32,6 +45,12 @@ pub trait Executor: Sealed + Send + Sync + Debug {
     async fn transaction(&self) -> Result<Transaction>;
 
     async fn get_cached_sql_statement_id(&self, statement: &str) -> Option<u64>;
+
+    /// Connection, through which requests, bound to single session (like execution
+    /// of prepared SQL statement), should be sent.
+    ///
+    /// `None` if all requests of this executor already go through single connection.
+    fn pinned_executor(&self) -> Option<Connection>;
 }
 
 #[async_trait]
@@ -40,7 +59,17 @@ impl<E: Executor + Sealed + Sync + Debug> Executor for &E {
         (**self).send_encoded_request(request).await
     }
 
-    fn stream(&self) -> Stream {
+    async fn send_encoded_request_with_push(
+        &self,
+        request: EncodedRequest,
+        push_tx: mpsc::UnboundedSender<Value>,
+    ) -> Result<Value> {
+        (**self)
+            .send_encoded_request_with_push(request, push_tx)
+            .await
+    }
+
+    fn stream(&self) -> Result<Stream> {
         (**self).stream()
     }
 
@@ -55,6 +84,10 @@ impl<E: Executor + Sealed + Sync + Debug> Executor for &E {
     async fn get_cached_sql_statement_id(&self, statement: &str) -> Option<u64> {
         (**self).get_cached_sql_statement_id(statement).await
     }
+
+    fn pinned_executor(&self) -> Option<Connection> {
+        (**self).pinned_executor()
+    }
 }
 
 #[async_trait]
@@ -63,7 +96,17 @@ impl<E: Executor + Sealed + Sync + Debug> Executor for &mut E {
         (**self).send_encoded_request(request).await
     }
 
-    fn stream(&self) -> Stream {
+    async fn send_encoded_request_with_push(
+        &self,
+        request: EncodedRequest,
+        push_tx: mpsc::UnboundedSender<Value>,
+    ) -> Result<Value> {
+        (**self)
+            .send_encoded_request_with_push(request, push_tx)
+            .await
+    }
+
+    fn stream(&self) -> Result<Stream> {
         (**self).stream()
     }
 
@@ -78,6 +121,10 @@ impl<E: Executor + Sealed + Sync + Debug> Executor for &mut E {
     async fn get_cached_sql_statement_id(&self, statement: &str) -> Option<u64> {
         (**self).get_cached_sql_statement_id(statement).await
     }
+
+    fn pinned_executor(&self) -> Option<Connection> {
+        (**self).pinned_executor()
+    }
 }
 
 #[cfg(test)]
@@ -2,16 +2,18 @@ use async_trait::async_trait;
 use futures::{future::BoxFuture, FutureExt};
 use rmpv::Value;
 use serde::de::DeserializeOwned;
+use tokio::sync::mpsc;
 
 use crate::{
     codec::request::{
-        Call, Delete, EncodedRequest, Eval, Execute, Insert, Ping, Prepare, Replace, Request,
-        Select, Update, Upsert,
+        Call, Call16, Delete, EncodedRequest, Eval, Execute, Insert, Ping, Prepare, Replace,
+        Request, Select, Update, Upsert,
     },
     schema::{SchemaEntityKey, Space},
     tuple::Tuple,
     utils::extract_and_deserialize_iproto_data,
-    CallResponse, DmoResponse, Executor, IteratorType, PreparedSqlStatement, Result, SqlResponse,
+    Call16Response, CallResponse, DmoResponse, Executor, IteratorType, PreparedSqlStatement,
+    ResponseWithPush, Result, SqlResponse,
 };
 
 /// Helper trait around [`Executor`] trait, which allows to send specific requests
@@ -26,6 +28,11 @@ pub trait ExecutorExt: Executor {
     where
         R: Request;
 
+    /// Send request, receiving out-of-band responses and raw final response body.
+    fn send_request_with_push<R>(&self, body: R) -> ResponseWithPush<'_>
+    where
+        R: Request;
+
     /// Ping tarantool instance.
     async fn ping(&self) -> Result<()> {
         self.send_request(Ping {}).await.map(drop)
@@ -60,6 +67,43 @@ pub trait ExecutorExt: Executor {
         ))
     }
 
+    /// Remotely call function in Tarantool, using `CALL_16` request.
+    ///
+    /// This is old format of call request, in which each returned value is
+    /// wrapped into tuple. Check [`Call16Response`] on how to deserialize response.
+    async fn call16<A, I>(&self, function_name: I, args: A) -> Result<Call16Response>
+    where
+        A: Tuple + Send,
+        I: AsRef<str> + Send + Sync,
+    {
+        Ok(Call16Response(
+            self.send_request(Call16::new(function_name.as_ref(), args))
+                .await?,
+        ))
+    }
+
+    /// Evaluate Lua expression, receiving values, sent with `box.session.push`.
+    ///
+    /// Check [`ResponseWithPush`] docs for details.
+    fn eval_with_push<A, I>(&self, expr: I, args: A) -> ResponseWithPush<'_>
+    where
+        A: Tuple + Send,
+        I: AsRef<str> + Send + Sync,
+    {
+        self.send_request_with_push(Eval::new(expr.as_ref(), args))
+    }
+
+    /// Remotely call function in Tarantool, receiving values, sent with `box.session.push`.
+    ///
+    /// Check [`ResponseWithPush`] docs for details.
+    fn call_with_push<A, I>(&self, function_name: I, args: A) -> ResponseWithPush<'_>
+    where
+        A: Tuple + Send,
+        I: AsRef<str> + Send + Sync,
+    {
+        self.send_request_with_push(Call::new(function_name.as_ref(), args))
+    }
+
     /// Select tuples from space.
     async fn select<T, A>(
         &self,
@@ -167,8 +211,14 @@ pub trait ExecutorExt: Executor {
     where
         I: AsRef<str> + Send + Sync,
     {
-        let response = self.send_request(Prepare::new(query.as_ref())).await?;
-        Ok(PreparedSqlStatement::from_prepare_response(response, self)?)
+        // Statement exists only in session, in which it was prepared
+        let pinned = self.pinned_executor();
+        let request = Prepare::new(query.as_ref());
+        let response = match pinned {
+            Some(ref conn) => conn.send_request(request).await?,
+            None => self.send_request(request).await?,
+        };
+        Ok(PreparedSqlStatement::from_prepare_response(response, self)?.pinned_to(pinned))
     }
 
     /// Find and load space by key.
@@ -207,6 +257,17 @@ impl<E: Executor + ?Sized> ExecutorExt for E {
         let req = EncodedRequest::new(body, None);
         async move { (*self).send_encoded_request(req?).await }.boxed()
     }
+
+    fn send_request_with_push<R>(&self, body: R) -> ResponseWithPush<'_>
+    where
+        R: Request,
+    {
+        let req = EncodedRequest::new(body, None);
+        let (push_tx, push_rx) = mpsc::unbounded_channel();
+        let fut =
+            async move { (*self).send_encoded_request_with_push(req?, push_tx).await }.boxed();
+        ResponseWithPush::new(fut, push_rx)
+    }
 }
 
 #[cfg(test)]
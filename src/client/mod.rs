@@ -1,14 +1,19 @@
 pub use self::{
-    call_response::CallResponse,
+    call_response::{Call16Response, CallResponse},
     connection::Connection,
     dmo::{DmoOperation, DmoResponse},
     executor::Executor,
     executor_ext::ExecutorExt,
+    pool::{Pool, PoolBalancing},
+    push::ResponseWithPush,
     sql::{PreparedSqlStatement, SqlResponse},
     stream::Stream,
     transaction::{Transaction, TransactionBuilder},
+    watcher::Watcher,
 };
 
+pub(crate) use self::retry::Retrier;
+
 // TODO: either reimport everything from schema or add dmo and sql mods
 pub mod schema;
 
@@ -17,17 +22,22 @@ mod connection;
 mod dmo;
 mod executor;
 mod executor_ext;
+mod pool;
+mod push;
+mod retry;
 mod sql;
 mod stream;
 mod transaction;
+mod watcher;
 
 mod private {
-    use crate::client::{Connection, Stream, Transaction};
+    use crate::client::{Connection, Pool, Stream, Transaction};
 
     #[doc(hidden)]
     pub trait Sealed {}
 
     impl Sealed for Connection {}
+    impl Sealed for Pool {}
     impl Sealed for Stream {}
     impl Sealed for Transaction {}
     impl<S: Sealed + ?Sized> Sealed for &S {}
@@ -0,0 +1,170 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use async_trait::async_trait;
+use rmpv::Value;
+use tokio::sync::mpsc;
+
+use super::{Connection, Stream, Transaction, TransactionBuilder};
+use crate::{codec::request::EncodedRequest, Executor, Result};
+
+/// Strategy of choosing connection in [`Pool`] for next request.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PoolBalancing {
+    /// Pick connections one after another (default).
+    #[default]
+    RoundRobin,
+    /// Pick connection with the least number of requests, currently waiting for response.
+    LeastInFlight,
+}
+
+/// Pool of connections to one or multiple Tarantool instances.
+///
+/// Each request is sent through one of underlying [`Connection`]s, chosen according to
+/// [`PoolBalancing`]. Each connection have its own dispatcher and reconnects independently.
+///
+/// [`Stream`]s and [`Transaction`]s, created from pool, are bound to single connection,
+/// which was chosen when they were created, so all their requests go to the same instance.
+///
+/// Since prepared SQL statements live in session on specific instance, pool doesn't cache
+/// SQL statements, and statements, prepared with
+/// [`ExecutorExt::prepare_sql`](crate::ExecutorExt::prepare_sql), are bound to connection,
+/// on which they were prepared.
+#[derive(Clone)]
+pub struct Pool {
+    inner: Arc<PoolInner>,
+}
+
+struct PoolInner {
+    members: Vec<PoolMember>,
+    balancing: PoolBalancing,
+    next_idx: AtomicUsize,
+}
+
+struct PoolMember {
+    conn: Connection,
+    in_flight: AtomicUsize,
+}
+
+impl Pool {
+    pub(crate) fn new(connections: Vec<Connection>, balancing: PoolBalancing) -> Self {
+        Self {
+            inner: Arc::new(PoolInner {
+                members: connections
+                    .into_iter()
+                    .map(|conn| PoolMember {
+                        conn,
+                        in_flight: AtomicUsize::new(0),
+                    })
+                    .collect(),
+                balancing,
+                next_idx: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Number of connections in pool.
+    pub fn len(&self) -> usize {
+        self.inner.members.len()
+    }
+
+    /// Whether pool have no connections.
+    pub fn is_empty(&self) -> bool {
+        self.inner.members.is_empty()
+    }
+
+    /// Iterate over all connections in pool.
+    pub fn connections(&self) -> impl Iterator<Item = &Connection> {
+        self.inner.members.iter().map(|x| &x.conn)
+    }
+
+    fn next_member(&self) -> &PoolMember {
+        let members = &self.inner.members;
+        let start = self.inner.next_idx.fetch_add(1, Ordering::Relaxed) % members.len();
+        match self.inner.balancing {
+            PoolBalancing::RoundRobin => &members[start],
+            // Start from next round-robin position, so connections with equal number
+            // of in-flights are still picked evenly.
+            PoolBalancing::LeastInFlight => (0..members.len())
+                .map(|offset| &members[(start + offset) % members.len()])
+                .min_by_key(|x| x.in_flight.load(Ordering::Relaxed))
+                .expect("Pool always have at least one connection"),
+        }
+    }
+
+    fn next_connection(&self) -> &Connection {
+        &self.next_member().conn
+    }
+}
+
+/// Decrements in-flight counter of pool member, even if request future was dropped.
+struct InFlightGuard<'a>(&'a AtomicUsize);
+
+impl<'a> InFlightGuard<'a> {
+    fn new(counter: &'a AtomicUsize) -> Self {
+        counter.fetch_add(1, Ordering::Relaxed);
+        Self(counter)
+    }
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[async_trait]
+impl Executor for Pool {
+    async fn send_encoded_request(&self, request: EncodedRequest) -> Result<Value> {
+        let member = self.next_member();
+        let _guard = InFlightGuard::new(&member.in_flight);
+        member.conn.send_encoded_request(request).await
+    }
+
+    async fn send_encoded_request_with_push(
+        &self,
+        request: EncodedRequest,
+        push_tx: mpsc::UnboundedSender<Value>,
+    ) -> Result<Value> {
+        let member = self.next_member();
+        let _guard = InFlightGuard::new(&member.in_flight);
+        member
+            .conn
+            .send_encoded_request_with_push(request, push_tx)
+            .await
+    }
+
+    fn stream(&self) -> Result<Stream> {
+        self.next_connection().stream()
+    }
+
+    fn transaction_builder(&self) -> TransactionBuilder {
+        self.next_connection().transaction_builder()
+    }
+
+    async fn transaction(&self) -> Result<Transaction> {
+        self.next_connection().transaction().await
+    }
+
+    async fn get_cached_sql_statement_id(&self, _statement: &str) -> Option<u64> {
+        None
+    }
+
+    fn pinned_executor(&self) -> Option<Connection> {
+        Some(self.next_connection().clone())
+    }
+}
+
+impl fmt::Debug for Pool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pool")
+            .field("size", &self.inner.members.len())
+            .field("balancing", &self.inner.balancing)
+            .finish()
+    }
+}
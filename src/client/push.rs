@@ -0,0 +1,102 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{future::BoxFuture, FutureExt};
+use rmpv::Value;
+use tokio::sync::mpsc;
+
+use crate::{CallResponse, Result};
+
+/// Response of `call` or `eval` request, which can send out-of-band
+/// values with `box.session.push` ([docs](https://www.tarantool.io/en/doc/latest/reference/reference_lua/box_session/push/)).
+///
+/// This type is a [`Stream`](futures::Stream) of pushed values, which ends
+/// when final response received. Final response can be retrieved with
+/// [`ResponseWithPush::response`].
+///
+/// Request is sent only when this type is polled.
+///
+/// # Example
+///
+/// ```rust,compile
+/// use futures::StreamExt;
+/// use tarantool_rs::{Connection, ExecutorExt};
+///
+/// # async fn async_wrapper() {
+/// let connection = Connection::builder().build("localhost:3301").await.unwrap();
+///
+/// let mut resp = connection.eval_with_push(
+///     "for i = 1, 3 do box.session.push(i) end; return 'done'",
+///     (),
+/// );
+/// while let Some(pushed) = resp.next().await {
+///     println!("Pushed: {}", pushed);
+/// }
+/// let result: String = resp.response().await.unwrap().decode_first().unwrap();
+/// # }
+/// ```
+pub struct ResponseWithPush<'a> {
+    response: FinalResponse<'a>,
+    push_rx: mpsc::UnboundedReceiver<Value>,
+}
+
+enum FinalResponse<'a> {
+    Pending(BoxFuture<'a, Result<Value>>),
+    Ready(Result<Value>),
+}
+
+impl<'a> ResponseWithPush<'a> {
+    pub(crate) fn new(
+        response_future: BoxFuture<'a, Result<Value>>,
+        push_rx: mpsc::UnboundedReceiver<Value>,
+    ) -> Self {
+        Self {
+            response: FinalResponse::Pending(response_future),
+            push_rx,
+        }
+    }
+
+    /// Wait for final response, dropping all not consumed pushed values.
+    pub async fn response(self) -> Result<CallResponse> {
+        let response = match self.response {
+            FinalResponse::Pending(fut) => fut.await,
+            FinalResponse::Ready(x) => x,
+        };
+        response.map(CallResponse)
+    }
+
+    /// Poll response future (if it is not finished yet) and store its result.
+    fn poll_response(&mut self, cx: &mut Context<'_>) {
+        if let FinalResponse::Pending(ref mut fut) = self.response {
+            if let Poll::Ready(x) = fut.poll_unpin(cx) {
+                // Drop future, so all senders of pushed values are dropped as well
+                self.response = FinalResponse::Ready(x);
+            }
+        }
+    }
+}
+
+impl futures::Stream for ResponseWithPush<'_> {
+    type Item = Value;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_response(cx);
+        // Channel is closed only after final response received (or request failed),
+        // so stream is finished only after final response is available.
+        self.push_rx.poll_recv(cx)
+    }
+}
+
+impl fmt::Debug for ResponseWithPush<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseWithPush")
+            .field(
+                "finished",
+                &matches!(self.response, FinalResponse::Ready(_)),
+            )
+            .finish()
+    }
+}
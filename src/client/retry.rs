@@ -0,0 +1,66 @@
+use parking_lot::Mutex;
+
+use crate::RetryPolicy;
+
+/// Retry policy with budget of retries, shared by connection and its clones.
+#[derive(Debug)]
+pub(crate) struct Retrier {
+    policy: RetryPolicy,
+    tokens: Mutex<f64>,
+}
+
+impl Retrier {
+    pub(crate) fn new(policy: RetryPolicy) -> Self {
+        let tokens = Mutex::new(policy.budget_max_tokens().into());
+        Self { policy, tokens }
+    }
+
+    pub(crate) fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    /// Take token for retry from budget. Return `false` if budget is exhausted.
+    pub(crate) fn try_withdraw(&self) -> bool {
+        let mut tokens = self.tokens.lock();
+        if *tokens < 1.0 {
+            return false;
+        }
+        *tokens -= 1.0;
+        true
+    }
+
+    /// Return tokens to budget after successful request.
+    pub(crate) fn deposit(&self) {
+        let mut tokens = self.tokens.lock();
+        *tokens =
+            (*tokens + self.policy.budget_refill()).min(self.policy.budget_max_tokens().into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_budget() {
+        let mut policy = RetryPolicy::default();
+        policy.budget(2, 0.5);
+        let retrier = Retrier::new(policy);
+        assert!(retrier.try_withdraw());
+        assert!(retrier.try_withdraw());
+        assert!(!retrier.try_withdraw());
+
+        retrier.deposit();
+        assert!(!retrier.try_withdraw());
+        retrier.deposit();
+        assert!(retrier.try_withdraw());
+
+        // Budget is not refilled beyond maximum
+        for _ in 0..10 {
+            retrier.deposit();
+        }
+        assert!(retrier.try_withdraw());
+        assert!(retrier.try_withdraw());
+        assert!(!retrier.try_withdraw());
+    }
+}
@@ -6,18 +6,30 @@ use crate::{
     codec::{consts::keys, request::Execute},
     errors::DecodingError,
     utils::{find_and_take_single_key_in_map, value_to_map},
-    Executor, ExecutorExt, Result, SqlResponse, Tuple,
+    Connection, Executor, ExecutorExt, Result, SqlResponse, Tuple,
 };
 
 #[derive(Debug)]
 pub struct PreparedSqlStatement<E> {
     stmt_id: u64,
     executor: E,
+    /// Connection, on which statement was prepared, if executor
+    /// can send requests through different connections.
+    pinned: Option<Connection>,
 }
 
 impl<E> PreparedSqlStatement<E> {
     fn new(stmt_id: u64, executor: E) -> Self {
-        Self { stmt_id, executor }
+        Self {
+            stmt_id,
+            executor,
+            pinned: None,
+        }
+    }
+
+    pub(crate) fn pinned_to(mut self, conn: Option<Connection>) -> Self {
+        self.pinned = conn;
+        self
     }
 
     pub fn from_prepare_response(response: Value, executor: E) -> StdResult<Self, DecodingError> {
@@ -40,6 +52,7 @@ impl<E: Clone> Clone for PreparedSqlStatement<E> {
         Self {
             stmt_id: self.stmt_id,
             executor: self.executor.clone(),
+            pinned: self.pinned.clone(),
         }
     }
 }
@@ -50,11 +63,12 @@ impl<E: Executor> PreparedSqlStatement<E> {
     where
         T: Tuple + Send,
     {
-        Ok(SqlResponse(
-            self.executor
-                .send_request(Execute::new_statement_id(self.stmt_id, binds))
-                .await?,
-        ))
+        let request = Execute::new_statement_id(self.stmt_id, binds);
+        let response = match self.pinned {
+            Some(ref conn) => conn.send_request(request).await?,
+            None => self.executor.send_request(request).await?,
+        };
+        Ok(SqlResponse(response))
     }
 }
 
@@ -63,6 +77,7 @@ impl<E: Clone> PreparedSqlStatement<&E> {
         PreparedSqlStatement {
             stmt_id: self.stmt_id,
             executor: self.executor.clone(),
+            pinned: self.pinned.clone(),
         }
     }
 }
@@ -3,6 +3,7 @@ use std::fmt;
 use async_trait::async_trait;
 
 use rmpv::Value;
+use tokio::sync::mpsc;
 
 use super::{Connection, Transaction, TransactionBuilder};
 use crate::{codec::request::EncodedRequest, Executor, Result};
@@ -31,7 +32,7 @@ use crate::{codec::request::EncodedRequest, Executor, Result};
 /// let _ = tokio::join!(eval_slow_fut, eval_fast_fut);
 ///
 /// // This will print 'slow' and then 'fast', since slow request was created first and have smaller sync
-/// let stream = connection.stream();
+/// let stream = connection.stream().unwrap();
 /// let eval_slow_fut = stream
 ///     .eval("fiber = require('fiber'); fiber.sleep(0.5); return ...;", ("slow", ))
 ///     .inspect(|res| println!("{:?}", res));
@@ -63,7 +64,18 @@ impl Executor for Stream {
         self.conn.send_encoded_request(request).await
     }
 
-    fn stream(&self) -> Stream {
+    async fn send_encoded_request_with_push(
+        &self,
+        mut request: EncodedRequest,
+        push_tx: mpsc::UnboundedSender<Value>,
+    ) -> Result<Value> {
+        request.stream_id = Some(self.stream_id);
+        self.conn
+            .send_encoded_request_with_push(request, push_tx)
+            .await
+    }
+
+    fn stream(&self) -> Result<Stream> {
         self.conn.stream()
     }
 
@@ -78,6 +90,10 @@ impl Executor for Stream {
     async fn get_cached_sql_statement_id(&self, statement: &str) -> Option<u64> {
         self.conn.get_cached_sql_statement_id(statement).await
     }
+
+    fn pinned_executor(&self) -> Option<Connection> {
+        None
+    }
 }
 
 impl fmt::Debug for Stream {
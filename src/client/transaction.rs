@@ -3,6 +3,7 @@ use std::{fmt, time::Duration};
 use async_trait::async_trait;
 
 use rmpv::Value;
+use tokio::sync::mpsc;
 use tracing::debug;
 
 use super::{Connection, ExecutorExt, Stream};
@@ -11,7 +12,7 @@ use crate::{
         consts::TransactionIsolationLevel,
         request::{Begin, Commit, EncodedRequest, Rollback},
     },
-    Executor, Result,
+    Executor, ProtocolFeature, Result,
 };
 
 /// Started transaction ([docs](https://www.tarantool.io/en/doc/latest/dev_guide/internals/box_protocol/#binary-protocol-streams)).
@@ -33,6 +34,7 @@ impl Transaction {
         isolation_level: TransactionIsolationLevel,
     ) -> Result<Self> {
         let stream_id = conn.next_stream_id();
+        conn.register_transaction(stream_id);
         let this = Self {
             conn,
             stream_id,
@@ -57,6 +59,8 @@ impl Transaction {
     pub async fn commit(mut self) -> Result<()> {
         if !self.finished {
             debug!("Commiting tranasction on stream {}", self.stream_id);
+            // Transaction is finished, so it shouldn't be rolled back on close
+            self.conn.unregister_transaction(self.stream_id);
             let _ = self.send_request(Commit::default()).await?;
             self.finished = true;
         }
@@ -67,6 +71,7 @@ impl Transaction {
     pub async fn rollback(mut self) -> Result<()> {
         if !self.finished {
             debug!("Rolling back tranasction on stream {}", self.stream_id);
+            self.conn.unregister_transaction(self.stream_id);
             let _ = self.send_request(Rollback::default()).await?;
             self.finished = true;
         }
@@ -76,6 +81,7 @@ impl Transaction {
 
 impl Drop for Transaction {
     fn drop(&mut self) {
+        self.conn.unregister_transaction(self.stream_id);
         if !self.finished {
             debug!(
                 "Rolling back tranasction on stream {} (on drop)",
@@ -95,8 +101,19 @@ impl Executor for Transaction {
         self.conn.send_encoded_request(request).await
     }
 
+    async fn send_encoded_request_with_push(
+        &self,
+        mut request: EncodedRequest,
+        push_tx: mpsc::UnboundedSender<Value>,
+    ) -> Result<Value> {
+        request.stream_id = Some(self.stream_id);
+        self.conn
+            .send_encoded_request_with_push(request, push_tx)
+            .await
+    }
+
     // TODO: do we need to repeat this in all ConnetionLike implementations?
-    fn stream(&self) -> Stream {
+    fn stream(&self) -> Result<Stream> {
         self.conn.stream()
     }
 
@@ -111,6 +128,10 @@ impl Executor for Transaction {
     async fn get_cached_sql_statement_id(&self, statement: &str) -> Option<u64> {
         self.conn.get_cached_sql_statement_id(statement).await
     }
+
+    fn pinned_executor(&self) -> Option<Connection> {
+        None
+    }
 }
 
 impl fmt::Debug for Transaction {
@@ -152,7 +173,13 @@ impl TransactionBuilder {
         self
     }
 
+    /// Begin transaction.
+    ///
+    /// Return [`Error::UnsupportedFeature`](crate::Error::UnsupportedFeature) if transactions
+    /// are not enabled for connection.
     pub async fn begin(&self) -> Result<Transaction> {
+        self.connection
+            .require_feature(ProtocolFeature::Transactions)?;
         Transaction::new(
             self.connection.clone(),
             self.timeout_secs,
@@ -0,0 +1,68 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use rmpv::Value;
+use tokio::sync::mpsc;
+
+use crate::transport::WatcherSubscription;
+
+/// Stream of notifications about changes of the key ([docs](https://www.tarantool.io/en/doc/latest/reference/reference_lua/box_events/)).
+///
+/// First value is current value of the key (or `nil`, if key was not broadcasted yet),
+/// next values are produced each time key updated on server with `box.broadcast`.
+///
+/// Subscription is kept on server as long as at least one `Watcher` for that key exists.
+/// If connection is restored after failure, key is watched again automatically.
+///
+/// Stream finishes only when underlying connection is closed permanently.
+///
+/// # Example
+///
+/// ```rust,compile
+/// use futures::StreamExt;
+/// use tarantool_rs::Connection;
+///
+/// # async fn async_wrapper() {
+/// let connection = Connection::builder().build("localhost:3301").await.unwrap();
+///
+/// let mut watcher = connection.watch("config").unwrap();
+/// while let Some(value) = watcher.next().await {
+///     println!("New config: {}", value);
+/// }
+/// # }
+/// ```
+pub struct Watcher {
+    rx: mpsc::UnboundedReceiver<Value>,
+    subscription: WatcherSubscription,
+}
+
+impl Watcher {
+    pub(crate) fn new(
+        subscription: WatcherSubscription,
+        rx: mpsc::UnboundedReceiver<Value>,
+    ) -> Self {
+        Self { rx, subscription }
+    }
+
+    /// Key, watched by this `Watcher`.
+    pub fn key(&self) -> &str {
+        self.subscription.key()
+    }
+}
+
+impl futures::Stream for Watcher {
+    type Item = Value;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl fmt::Debug for Watcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Watcher").field("key", &self.key()).finish()
+    }
+}
@@ -32,7 +32,10 @@ pub mod keys {
     pub const VERSION: u8 = 0x54;
     pub const FEATURES: u8 = 0x55;
     pub const TIMEOUT: u8 = 0x56;
+    pub const EVENT_KEY: u8 = 0x57;
+    pub const EVENT_DATA: u8 = 0x58;
     pub const TXN_ISOLATION: u8 = 0x59;
+    pub const AUTH_TYPE: u8 = 0x5b;
 }
 
 /// Request type constants.
@@ -74,6 +77,7 @@ pub enum RequestType {
 
 pub mod response_codes {
     pub const OK: u32 = 0x0;
+    pub const CHUNK: u32 = 0x80;
     pub const ERROR_RANGE_START: u32 = 0x8000;
     pub const ERROR_RANGE_END: u32 = 0x8FFF;
 }
@@ -100,6 +104,37 @@ impl Default for TransactionIsolationLevel {
     }
 }
 
+/// Authentication method.
+///
+/// See docs [here](https://www.tarantool.io/en/doc/latest/reference/configuration/#cfg-auth-type).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum AuthMethod {
+    /// `chap-sha1`, supported by all Tarantool versions.
+    #[default]
+    ChapSha1,
+    /// `pap-sha256`, available only in Tarantool Enterprise.
+    ///
+    /// Password is sent in plain text, so this method should be used only with TLS.
+    PapSha256,
+}
+
+impl AuthMethod {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::ChapSha1 => "chap-sha1",
+            Self::PapSha256 => "pap-sha256",
+        }
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "chap-sha1" => Some(Self::ChapSha1),
+            "pap-sha256" => Some(Self::PapSha256),
+            _ => None,
+        }
+    }
+}
+
 /// Iterator type for `select` requests.
 ///
 /// For details check Tarantool documantation <https://www.tarantool.io/en/doc/latest/reference/reference_lua/box_index/pairs/>.
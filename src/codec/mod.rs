@@ -5,7 +5,7 @@ use rmp::Marker;
 use tokio_util::codec::{Decoder, Encoder};
 use tracing::trace;
 
-use self::{request::EncodedRequest, response::Response};
+use self::{request::EncodedRequest, response::IncomingMessage};
 use crate::{
     errors::{CodecDecodeError, CodecEncodeError, DecodingError},
     Error,
@@ -101,7 +101,7 @@ pub(crate) struct ClientCodec {
 }
 
 impl Decoder for ClientCodec {
-    type Item = Response;
+    type Item = IncomingMessage;
 
     type Error = CodecDecodeError;
 
@@ -116,7 +116,7 @@ impl Decoder for ClientCodec {
         if src.len() >= next_frame_length {
             self.length_decoder.reset();
             let frame_bytes = src.split_to(next_frame_length);
-            Response::decode(frame_bytes.reader())
+            IncomingMessage::decode(frame_bytes.reader())
                 .map(Some)
                 .map_err(CodecDecodeError::Decode)
         } else {
@@ -156,7 +156,7 @@ impl Encoder<EncodedRequest> for ClientCodec {
 /// Greeting message from server.
 ///
 /// [Docs](https://www.tarantool.io/en/doc/latest/dev_guide/internals/box_protocol/#greeting-message).
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Greeting {
     pub server: String,
     pub salt: Vec<u8>,
@@ -7,7 +7,7 @@ use sha1::{Digest, Sha1};
 use super::Request;
 use crate::{
     codec::{
-        consts::{keys, RequestType},
+        consts::{keys, AuthMethod, RequestType},
         utils::write_kv_str,
     },
     errors::EncodingError,
@@ -16,14 +16,26 @@ use crate::{
 #[derive(Clone, Debug)]
 pub(crate) struct Auth<'a> {
     pub user_name: &'a str,
+    pub method: AuthMethod,
     pub scramble: Vec<u8>,
 }
 
 impl<'a> Auth<'a> {
-    pub(crate) fn new(user: &'a str, password: Option<&'a str>, salt: &'a [u8]) -> Self {
+    pub(crate) fn new(
+        user: &'a str,
+        password: Option<&'a str>,
+        salt: &'a [u8],
+        method: AuthMethod,
+    ) -> Self {
+        let scramble = match method {
+            AuthMethod::ChapSha1 => prepare_scramble(password, salt),
+            // Password is sent as is, server hashes it itself
+            AuthMethod::PapSha256 => password.unwrap_or("").as_bytes().to_vec(),
+        };
         Self {
             user_name: user,
-            scramble: prepare_scramble(password, salt),
+            method,
+            scramble,
         }
     }
 }
@@ -42,8 +54,14 @@ impl<'a> Request for Auth<'a> {
         write_kv_str(&mut buf, keys::USER_NAME, self.user_name)?;
         rmp::encode::write_pfix(&mut buf, keys::TUPLE)?;
         rmp::encode::write_array_len(&mut buf, 2)?;
-        rmp::encode::write_str(&mut buf, "chap-sha1")?;
-        rmp::encode::write_bin(&mut buf, &self.scramble)?;
+        rmp::encode::write_str(&mut buf, self.method.name())?;
+        match self.method {
+            AuthMethod::ChapSha1 => rmp::encode::write_bin(&mut buf, &self.scramble)?,
+            AuthMethod::PapSha256 => {
+                rmp::encode::write_str_len(&mut buf, self.scramble.len() as u32)?;
+                buf.write_all(&self.scramble)?;
+            }
+        }
         Ok(())
     }
 }
@@ -4,9 +4,10 @@ use bytes::{BufMut, Bytes, BytesMut};
 use crate::errors::EncodingError;
 
 pub(crate) use self::{
-    auth::Auth, begin::Begin, call::Call, commit::Commit, delete::Delete, eval::Eval,
-    execute::Execute, id::Id, insert::Insert, ping::Ping, prepare::Prepare, replace::Replace,
-    rollback::Rollback, select::Select, update::Update, upsert::Upsert,
+    auth::Auth, begin::Begin, call::Call, call16::Call16, commit::Commit, delete::Delete,
+    eval::Eval, execute::Execute, id::Id, insert::Insert, ping::Ping, prepare::Prepare,
+    replace::Replace, rollback::Rollback, select::Select, unwatch::Unwatch, update::Update,
+    upsert::Upsert, watch::Watch,
 };
 
 use std::io::Write;
@@ -16,6 +17,7 @@ use super::consts::{keys, RequestType};
 mod auth;
 mod begin;
 mod call;
+mod call16;
 mod commit;
 mod delete;
 mod eval;
@@ -27,8 +29,10 @@ mod prepare;
 mod replace;
 mod rollback;
 mod select;
+mod unwatch;
 mod update;
 mod upsert;
+mod watch;
 
 pub const PROTOCOL_VERSION: u8 = 3;
 
@@ -54,6 +58,7 @@ pub trait Request {
 
 /// Request, encoded into MessagePack, and its meta data.
 #[doc(hidden)]
+#[derive(Clone)]
 pub struct EncodedRequest {
     /// By default `sync` is set to 0 and replaced with
     /// actual value when reaching [`crate::transport::Connection`].
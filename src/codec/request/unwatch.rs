@@ -0,0 +1,41 @@
+use std::io::Write;
+
+use crate::{
+    codec::{
+        consts::{keys, RequestType},
+        utils::write_kv_str,
+    },
+    errors::EncodingError,
+};
+
+use super::Request;
+
+/// Unsubscribe from event.
+///
+/// Server doesn't respond to this request.
+#[derive(Clone, Debug)]
+pub(crate) struct Unwatch<'a> {
+    pub key: &'a str,
+}
+
+impl<'a> Request for Unwatch<'a> {
+    fn request_type() -> RequestType
+    where
+        Self: Sized,
+    {
+        RequestType::Unwatch
+    }
+
+    // NOTE: `&mut buf: mut` is required since I don't get why compiler complain
+    fn encode(&self, mut buf: &mut dyn Write) -> Result<(), EncodingError> {
+        rmp::encode::write_map_len(&mut buf, 1)?;
+        write_kv_str(buf, keys::EVENT_KEY, self.key)?;
+        Ok(())
+    }
+}
+
+impl<'a> Unwatch<'a> {
+    pub fn new(key: &'a str) -> Self {
+        Self { key }
+    }
+}
@@ -0,0 +1,42 @@
+use std::io::Write;
+
+use crate::{
+    codec::{
+        consts::{keys, RequestType},
+        utils::write_kv_str,
+    },
+    errors::EncodingError,
+};
+
+use super::Request;
+
+/// Subscribe to event or acknowledge previous notification for event.
+///
+/// Server doesn't respond to this request directly, instead it sends
+/// `EVENT` packet when value of the key is updated.
+#[derive(Clone, Debug)]
+pub(crate) struct Watch<'a> {
+    pub key: &'a str,
+}
+
+impl<'a> Request for Watch<'a> {
+    fn request_type() -> RequestType
+    where
+        Self: Sized,
+    {
+        RequestType::Watch
+    }
+
+    // NOTE: `&mut buf: mut` is required since I don't get why compiler complain
+    fn encode(&self, mut buf: &mut dyn Write) -> Result<(), EncodingError> {
+        rmp::encode::write_map_len(&mut buf, 1)?;
+        write_kv_str(buf, keys::EVENT_KEY, self.key)?;
+        Ok(())
+    }
+}
+
+impl<'a> Watch<'a> {
+    pub fn new(key: &'a str) -> Self {
+        Self { key }
+    }
+}
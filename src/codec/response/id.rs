@@ -0,0 +1,58 @@
+use rmpv::Value;
+use tracing::warn;
+
+use crate::{
+    codec::consts::{keys, AuthMethod},
+    errors::DecodingError,
+    utils::value_to_map,
+};
+
+/// Body of response to ID request ([docs](https://www.tarantool.io/en/doc/latest/dev_guide/internals/iproto/keys/#iproto-id)).
+#[derive(Clone, Debug, Default)]
+pub(crate) struct IdResponse {
+    pub protocol_version: u64,
+    pub features: Vec<u64>,
+    /// Authentication method, set by `box.cfg.auth_type` on server.
+    ///
+    /// Not sent by old versions of Tarantool.
+    pub auth_method: Option<AuthMethod>,
+}
+
+impl IdResponse {
+    pub(crate) fn decode(body: Value) -> Result<Self, DecodingError> {
+        let map = value_to_map(body).map_err(|err| err.in_other("ID response body"))?;
+        let mut this = Self::default();
+        for (k, v) in map {
+            let Some(k) = k.as_u64() else {
+                continue;
+            };
+            match k {
+                k if k == keys::VERSION as u64 => {
+                    this.protocol_version = v.as_u64().ok_or_else(|| {
+                        DecodingError::type_mismatch("u64", v.to_string()).in_key("VERSION")
+                    })?;
+                }
+                k if k == keys::FEATURES as u64 => {
+                    let Value::Array(features) = v else {
+                        return Err(
+                            DecodingError::type_mismatch("array", v.to_string()).in_key("FEATURES")
+                        );
+                    };
+                    this.features = features.iter().filter_map(Value::as_u64).collect();
+                }
+                k if k == keys::AUTH_TYPE as u64 => {
+                    let Some(name) = v.as_str() else {
+                        return Err(DecodingError::type_mismatch("string", v.to_string())
+                            .in_key("AUTH_TYPE"));
+                    };
+                    this.auth_method = AuthMethod::from_name(name);
+                    if this.auth_method.is_none() {
+                        warn!("Server advertised unknown auth method '{}'", name);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(this)
+    }
+}
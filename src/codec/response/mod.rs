@@ -1,20 +1,27 @@
 use std::io::Read;
 
+use rmpv::Value;
 use tracing::{debug, error};
 
-use super::consts::response_codes::{ERROR_RANGE_END, ERROR_RANGE_START, OK};
+use super::consts::response_codes::{CHUNK, ERROR_RANGE_END, ERROR_RANGE_START, OK};
 use crate::{
-    codec::consts::keys,
+    codec::consts::{keys, RequestType},
     errors::{DecodingError, ErrorResponse},
+    utils::extract_iproto_data,
 };
 
-// TODO: add out-of-band (I.e. IPROTO_CHUNK)
+pub(crate) use self::id::IdResponse;
+
+mod id;
+
 // TODO: actually implement extra error data
 // TODO: create bodies for specific responses (for optimization reasons)
 #[derive(Clone, Debug)]
 pub(crate) enum ResponseBody {
     Ok(rmpv::Value), // TODO: replace
     Error(ErrorResponse),
+    /// Value from out-of-band (non-final) response, i.e. `IPROTO_CHUNK`.
+    Chunk(rmpv::Value),
 }
 
 #[derive(Clone, Debug)]
@@ -24,11 +31,21 @@ pub(crate) struct Response {
     pub body: ResponseBody,
 }
 
-impl Response {
-    // TODO: split function
-    // Use [`anyhow::Error`] because any error would mean either entirely broken
-    // implementation of protocol or underlying I/O error, which currently would be
-    // implementation bug as well.
+/// Notification about changed value of watched key.
+#[derive(Clone, Debug)]
+pub(crate) struct Event {
+    pub key: String,
+    pub data: rmpv::Value,
+}
+
+/// Any message, which can be received from server.
+#[derive(Clone, Debug)]
+pub(crate) enum IncomingMessage {
+    Response(Response),
+    Event(Event),
+}
+
+impl IncomingMessage {
     pub(super) fn decode(mut buf: impl Read) -> Result<Self, DecodingError> {
         let map_len = rmp::decode::read_map_len(&mut buf)?;
         let mut response_code: Option<u32> = None;
@@ -56,18 +73,77 @@ impl Response {
         let Some(response_code) = response_code else {
             return Err(DecodingError::missing_key("RESPONSE_CODE"));
         };
+        // Events are not bound to any request, so they don't have meaningful sync
+        if response_code == RequestType::Event as u32 {
+            return Event::decode(buf).map(Self::Event);
+        }
         let Some(sync) = sync else {
             return Err(DecodingError::missing_key("SYNC"));
         };
         let Some(schema_version) = schema_version else {
             return Err(DecodingError::missing_key("SCHEMA_VERSION"));
         };
+        Response::decode_body(buf, response_code, sync, schema_version).map(Self::Response)
+    }
+}
+
+impl Event {
+    fn decode(mut buf: impl Read) -> Result<Self, DecodingError> {
+        let mut key = None;
+        let mut data = None;
+        let map_len = rmp::decode::read_map_len(&mut buf)?;
+        for _ in 0..map_len {
+            let map_key: u8 = rmp::decode::read_pfix(&mut buf)?;
+            match map_key {
+                keys::EVENT_KEY => {
+                    key = Some(rmpv::ext::from_value(rmpv::decode::read_value(&mut buf)?)?);
+                }
+                keys::EVENT_DATA => {
+                    data = Some(rmpv::decode::read_value(&mut buf)?);
+                }
+                rest => {
+                    debug!("Unexpected key encountered in event body: {}", rest);
+                    let _ = rmpv::decode::read_value(&mut buf)?;
+                }
+            }
+        }
+        let Some(key) = key else {
+            return Err(DecodingError::missing_key("EVENT_KEY"));
+        };
+        Ok(Self {
+            key,
+            // Data is omitted, if key have no value
+            data: data.unwrap_or(rmpv::Value::Nil),
+        })
+    }
+}
+
+impl Response {
+    // Use [`anyhow::Error`] because any error would mean either entirely broken
+    // implementation of protocol or underlying I/O error, which currently would be
+    // implementation bug as well.
+    fn decode_body(
+        mut buf: impl Read,
+        response_code: u32,
+        sync: u32,
+        schema_version: u32,
+    ) -> Result<Self, DecodingError> {
         let body = match response_code {
             OK => {
                 let v = rmpv::decode::read_value(&mut buf)?;
                 debug!("{}", v);
                 ResponseBody::Ok(v)
             }
+            CHUNK => {
+                let v = rmpv::decode::read_value(&mut buf)?;
+                debug!("Chunk: {}", v);
+                // Pushed value is wrapped into array with single element
+                let v = match extract_iproto_data(v)? {
+                    Value::Array(mut x) if x.len() == 1 => x.pop().unwrap_or(Value::Nil),
+                    rest => rest,
+                };
+                ResponseBody::Chunk(v)
+            }
             code @ ERROR_RANGE_START..=ERROR_RANGE_END => {
                 let code = code - 0x8000;
                 let mut description = None;
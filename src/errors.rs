@@ -7,7 +7,9 @@ use rmp::{
     encode::{RmpWriteErr, ValueWriteError},
 };
 use rmpv::Value;
-use tokio::{task::JoinError, time::error::Elapsed};
+use tokio::task::JoinError;
+
+use crate::transport::ProtocolFeature;
 
 /// Error returned by Tarantool in response to a request.
 #[derive(Clone, Debug, thiserror::Error)]
@@ -39,9 +41,12 @@ pub enum Error {
     #[error("Call or eval error: {0}")]
     CallEval(Value),
 
-    /// Timeout.
-    #[error("Timeout")]
-    Timeout,
+    /// Request timeout.
+    ///
+    /// `sent` is `true` if request was already written to socket,
+    /// which means that it could be executed by server.
+    #[error("Timeout (request {})", if *.sent { "sent" } else { "not sent" })]
+    Timeout { sent: bool },
     /// Timeout while establishing connection.
     #[error("Connect timeout")]
     ConnectTimeout,
@@ -71,6 +76,36 @@ pub enum Error {
     /// Underlying TCP connection was closed.
     #[error("TCP connection closed")]
     ConnectionClosed,
+    /// Connection is not established at the moment and request
+    /// was rejected according to [`RequestPolicy`](crate::RequestPolicy).
+    #[error("Not connected")]
+    NotConnected,
+    /// Connection was lost and limit of reconnection attempts
+    /// (or duration) exceeded. Connection won't be reestablished.
+    #[error("Failed to reconnect after {attempts} attempts")]
+    ReconnectFailed { attempts: u32 },
+    /// Connection was closed, but some in-flight requests
+    /// were not finished before timeout and abandoned.
+    #[error("Connection closed by timeout, {abandoned} in-flight requests abandoned")]
+    CloseTimeout { abandoned: usize },
+    /// Instance did not pass check, set with
+    /// [`ConnectionBuilder::instance_check`](crate::ConnectionBuilder::instance_check).
+    #[error("Instance {address} did not pass check")]
+    InstanceCheckFailed { address: String },
+    /// Request requires protocol feature, which was not requested by client
+    /// or not advertised by server in response to ID request.
+    #[error(
+        "Feature {0:?} is not enabled for connection (not requested or not supported by server)"
+    )]
+    UnsupportedFeature(ProtocolFeature),
+    /// Connection URI can't be parsed
+    /// (check [`ConnectionBuilder::from_uri`](crate::ConnectionBuilder::from_uri)).
+    #[error("Invalid connection URI: {0}")]
+    InvalidUri(String),
+    /// Connection configuration contains invalid value
+    /// (check [`ConnectionConfig::overlay_env`](crate::ConnectionConfig::overlay_env)).
+    #[error("Invalid connection configuration: {0}")]
+    InvalidConfig(String),
 
     /// Other errors that don't deserve a separate variant.
     #[error(transparent)]
@@ -112,12 +147,6 @@ impl From<ConnectionError> for Error {
     }
 }
 
-impl From<Elapsed> for Error {
-    fn from(_: Elapsed) -> Self {
-        Self::Timeout
-    }
-}
-
 /// Errors, related to encoding requests.
 #[non_exhaustive]
 #[derive(Debug, thiserror::Error)]
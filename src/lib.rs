@@ -2,12 +2,13 @@
 //
 // Features:
 //
-// * [ ] connections pooling
-// * [ ] chunked responses (tt feature)
+// * [x] connections pooling
+// * [x] chunked responses (tt feature)
 // * [ ] streaming responses for select
 // * [ ] background schema fetching, reloading and invalidating
-// * [ ] triggers on connection events (connect/disconnect/schema reloading)
-// * [ ] graceful shutdown protocol
+// * [x] triggers on connection events (connect/disconnect)
+// * [ ] triggers on schema reloading
+// * [x] graceful shutdown protocol
 //
 // Other
 //
@@ -63,14 +64,17 @@
 //! * [x] CRUD operations
 //! * [x] transaction control (begin/commit/rollback)
 //! * [x] reconnection in background
+//! * [x] TCP and unix domain sockets
+//! * [x] TLS (with `tls` feature)
+//! * [x] SOCKS5 and HTTP CONNECT proxies
 //! * [ ] SQL requests
-//! * [ ] chunked responses
-//! * [ ] watchers and events
-//! * [ ] connection pooling
+//! * [x] chunked responses
+//! * [x] watchers and events
+//! * [x] connection pooling
 //! * [ ] automatic schema fetching and reloading
-//! * [ ] graceful shutdown protocol support
-//! * [ ] pre Tarantool 2.10 versions support
-//! * [ ] customizable connection features (streams/watchers/mvcc)
+//! * [x] graceful shutdown protocol support
+//! * [x] pre Tarantool 2.10 versions support
+//! * [x] customizable connection features (streams/watchers/mvcc)
 //! * [ ] custom Tarantool MP types (UUID, ...)
 //! * [ ] ...
 
@@ -78,13 +82,24 @@ pub use rmpv::Value;
 
 #[doc(inline)]
 pub use self::{
-    builder::{ConnectionBuilder, ReconnectInterval},
+    builder::{ConnectionBuilder, ConnectionConfig, ReconnectInterval, RequestPolicy, RetryPolicy},
     client::*,
-    codec::consts::{IteratorType, TransactionIsolationLevel},
+    codec::{
+        consts::{AuthMethod, IteratorType, TransactionIsolationLevel},
+        Greeting,
+    },
     errors::Error,
     tuple::{Tuple, TupleElement},
 };
 
+#[cfg(feature = "tls")]
+#[doc(inline)]
+pub use self::transport::TlsConfig;
+#[doc(inline)]
+pub use self::transport::{
+    ConnectionState, ProtocolFeature, ProxyConfig, Resolver, ServerInfo, Spawner, TarantoolVersion,
+};
+
 pub mod errors;
 pub mod utils;
 
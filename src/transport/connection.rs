@@ -1,41 +1,90 @@
-use std::{collections::HashMap, fmt::Display, time::Duration};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+    io,
+    net::SocketAddr,
+    sync::Arc,
+    time::Duration,
+};
 
 use futures::{
     future::{Fuse, FusedFuture},
-    FutureExt, SinkExt, StreamExt, TryStreamExt,
+    FutureExt, SinkExt, Stream, StreamExt, TryStreamExt,
 };
 
+use rmpv::Value;
 use tokio::{
     io::AsyncReadExt,
-    net::{
-        tcp::{OwnedReadHalf, OwnedWriteHalf},
-        TcpStream, ToSocketAddrs,
-    },
+    net::ToSocketAddrs,
     pin,
-    sync::mpsc,
-    task::JoinHandle,
+    sync::{mpsc, oneshot, Notify},
+    time::{sleep, Instant},
 };
-use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::{
     codec::{FramedRead, FramedWrite},
     sync::CancellationToken,
 };
 use tracing::{debug, error, trace, warn};
 
-use super::dispatcher::{DispatcherRequest, DispatcherResponse, DispatcherResponseSender};
+use super::{
+    dispatcher::{DispatcherRequest, DispatcherResponse, DispatcherResponseSender, SendState},
+    server_info::{ProtocolFeature, ServerInfo, TarantoolVersion},
+    spawner::Spawner,
+    stream::{Connector, ReadHalf, WriteHalf},
+    watchers::{WatcherCommand, Watchers, SHUTDOWN_EVENT_KEY},
+};
 use crate::{
     codec::{
-        request::{Auth, EncodedRequest},
-        response::{Response, ResponseBody},
+        consts::AuthMethod,
+        consts::RequestType,
+        request::{Auth, EncodedRequest, Eval, Id, Ping, Request, Unwatch, Watch},
+        response::{Event, IdResponse, IncomingMessage, Response, ResponseBody},
         ClientCodec, Greeting,
     },
-    errors::{CodecEncodeError, ConnectionError, Error},
-    utils::CancellableFuture,
+    errors::{CodecEncodeError, ConnectionError, Error, ErrorResponse},
+    utils::{extract_iproto_data, CancellableFuture},
 };
 
+/// Parameters of connection, shared between all reconnects.
+#[derive(Clone, Debug)]
+pub(crate) struct ConnectionParams {
+    pub user: Option<String>,
+    pub password: Option<String>,
+    pub auth_method: Option<AuthMethod>,
+    pub connect_timeout: Option<Duration>,
+    pub internal_simultaneous_requests_threshold: usize,
+    pub heartbeat_interval: Option<Duration>,
+    pub heartbeat_timeout: Duration,
+    /// Protocol version and features, requested from server.
+    pub id: Id,
+    /// Lua expression, which should return `true` for instance to be used.
+    pub instance_check: Option<String>,
+}
+
+/// Request to close connection.
+pub(crate) struct CloseRequest {
+    /// How long to wait for in-flight requests to finish.
+    pub timeout: Duration,
+    /// Receives number of abandoned in-flight requests after dispatcher is stopped.
+    pub tx: oneshot::Sender<usize>,
+}
+
+/// Reason of stopping connection without error.
+pub(crate) enum Stopped {
+    /// All senders are dropped.
+    SendersDropped,
+    /// Connection was closed by [`CloseRequest`].
+    Closed {
+        request: CloseRequest,
+        abandoned: usize,
+    },
+}
+
 struct ConnectionData {
     in_flights: HashMap<u32, DispatcherResponseSender>,
     next_sync: u32,
+    /// Sync of heartbeat request, which response is not yet received.
+    heartbeat_sync: Option<u32>,
 }
 
 impl Default for ConnectionData {
@@ -43,6 +92,7 @@ impl Default for ConnectionData {
         Self {
             in_flights: HashMap::with_capacity(5),
             next_sync: 0,
+            heartbeat_sync: None,
         }
     }
 }
@@ -106,6 +156,21 @@ impl ConnectionData {
         }
     }
 
+    /// Send out-of-band response (by sync) to client, keeping request in-flight.
+    #[inline]
+    fn push_to_client(&mut self, sync: u32, value: Value) {
+        if let Some(tx) = self.in_flights.get(&sync) {
+            if tx.push(value).is_err() {
+                debug!(
+                    "Out-of-band response for sync {} dropped, client doesn't accept them",
+                    sync
+                );
+            }
+        } else {
+            warn!("Unknown sync {}", sync);
+        }
+    }
+
     /// Send error to all in-flight requests and drop them.
     #[inline]
     fn send_error_to_all_in_flights(&mut self, err: ConnectionError) {
@@ -118,21 +183,62 @@ impl ConnectionData {
     #[inline]
     fn return_requests_to_be_resent(&mut self, requests: Vec<EncodedRequest>) {
         for x in requests {
+            // Watchers requests are internal and not awaited by anyone,
+            // they will be sent again after reconnect
+            if matches!(x.request_type, RequestType::Watch | RequestType::Unwatch) {
+                continue;
+            }
             self.respond_to_client(x.sync, x)
         }
     }
+
+    /// Prepare request, which doesn't have response from server.
+    fn prepare_request_without_response(&mut self, body: impl Request) -> Option<EncodedRequest> {
+        match EncodedRequest::new(body, None) {
+            Ok(mut request) => {
+                *request.sync_mut() = self.next_sync();
+                Some(request)
+            }
+            Err(err) => {
+                error!("Failed to encode internal request: {err:#}");
+                None
+            }
+        }
+    }
+
+    /// Prepare heartbeat request, response to which is handled internally.
+    fn prepare_heartbeat(&mut self) -> Option<EncodedRequest> {
+        let request = self.prepare_request_without_response(Ping {})?;
+        self.heartbeat_sync = Some(request.sync);
+        Some(request)
+    }
 }
 
 // NOTE: here is weird logic, where task can be cancelld using token and when
 // rx closed. Token is necessary to close task when it currently sending to socket.
 async fn writer_task(
-    mut rx: mpsc::Receiver<EncodedRequest>,
-    mut stream: FramedWrite<OwnedWriteHalf, ClientCodec>,
+    mut rx: mpsc::Receiver<WriterRequest>,
+    mut stream: FramedWrite<WriteHalf, ClientCodec>,
     cancellation_token: CancellationToken,
 ) -> (Result<(), (u32, CodecEncodeError)>, Vec<EncodedRequest>) {
     let mut result = Ok(());
 
-    while let Some(x) = rx.recv().await {
+    loop {
+        // Waiting for next request should be cancellable as well,
+        // since connection can be closed while there is nothing to send
+        let x = tokio::select! {
+            next = rx.recv() => match next {
+                Some(x) => x,
+                None => break,
+            },
+            _ = cancellation_token.cancelled() => break,
+        };
+        let (x, send_state) = x;
+        // Skip requests, which are no longer awaited by client
+        if send_state.is_some_and(|state| !state.try_mark_sent()) {
+            trace!("Request with sync {} cancelled before sending", x.sync);
+            continue;
+        }
         let sync = x.sync;
         let fut = CancellableFuture::new(stream.send(x), &cancellation_token);
         match fut.await {
@@ -153,7 +259,7 @@ async fn writer_task(
     rx.close();
     cancellation_token.cancel();
     let mut remaining_requests = Vec::new();
-    while let Ok(next) = rx.try_recv() {
+    while let Ok((next, _)) = rx.try_recv() {
         remaining_requests.push(next);
     }
 
@@ -165,136 +271,288 @@ async fn writer_task(
     (result, remaining_requests)
 }
 
-type WriterTaskJoinHandle = JoinHandle<(Result<(), (u32, CodecEncodeError)>, Vec<EncodedRequest>)>;
+/// Request for writer task with its state, if request is awaited by client.
+type WriterRequest = (EncodedRequest, Option<SendState>);
+
+/// Receiver of writer task's result and requests, which it failed to send.
+type WriterTaskResultReceiver =
+    oneshot::Receiver<(Result<(), (u32, CodecEncodeError)>, Vec<EncodedRequest>)>;
 
 pub(crate) struct Connection {
-    read_stream: FramedRead<OwnedReadHalf, ClientCodec>,
-    writer_tx: mpsc::Sender<EncodedRequest>,
-    writer_task_handle: WriterTaskJoinHandle,
+    read_stream: FramedRead<ReadHalf, ClientCodec>,
+    writer_tx: mpsc::Sender<WriterRequest>,
+    writer_task_result_rx: WriterTaskResultReceiver,
     writer_task_cancellation_token: CancellationToken,
     data: ConnectionData,
+    watchers: Arc<Watchers>,
+    greeting: Greeting,
+    server_info: Arc<ServerInfo>,
+    peer_addr: Option<SocketAddr>,
+    heartbeat: Option<(Duration, Duration)>,
 }
 
 impl Connection {
     async fn new_inner<A>(
-        addr: A,
-        user: Option<&str>,
-        password: Option<&str>,
-        internal_simultaneous_requests_threshold: usize,
+        connector: &Connector<A>,
+        params: &ConnectionParams,
+        watchers: Arc<Watchers>,
+        spawner: &dyn Spawner,
     ) -> Result<Self, Error>
     where
         A: ToSocketAddrs + Display,
     {
-        debug!("Starting connection to Tarantool {}", addr);
-        let mut tcp = TcpStream::connect(&addr).await?;
-        trace!("Connection established to {}", addr);
+        debug!("Starting connection to Tarantool {}", connector);
+        let (mut read_half, write_half, peer_addr) = connector.connect().await?;
+        trace!("Connection established to {} ({:?})", connector, peer_addr);
 
         let mut greeting_buffer = [0u8; Greeting::SIZE];
-        tcp.read_exact(&mut greeting_buffer).await?;
+        read_half.read_exact(&mut greeting_buffer).await?;
         let greeting = Greeting::decode(greeting_buffer)?;
         debug!("Server: {}", greeting.server);
         trace!("Salt: {:?}", greeting.salt);
 
-        let (read_tcp_stream, write_tcp_stream) = tcp.into_split();
-        let mut read_stream = FramedRead::new(read_tcp_stream, ClientCodec::default());
-        let mut write_stream = FramedWrite::new(write_tcp_stream, ClientCodec::default());
+        let mut read_stream = FramedRead::new(read_half, ClientCodec::default());
+        let mut write_stream = FramedWrite::new(write_half, ClientCodec::default());
 
         let mut conn_data = ConnectionData::default();
 
-        if let Some(user) = user {
+        // Servers before 2.10 don't know ID request and don't support any features
+        let id_response = match TarantoolVersion::from_greeting(&greeting) {
+            Some(x) if !x.supports_id_request() => {
+                debug!(
+                    "Skipping ID request, since it is not supported by server {}",
+                    x
+                );
+                IdResponse::default()
+            }
+            _ => {
+                Self::id(
+                    &mut read_stream,
+                    &mut write_stream,
+                    conn_data.next_sync(),
+                    &params.id,
+                )
+                .await?
+            }
+        };
+
+        if let Some(ref user) = params.user {
+            // Use method, advertised by server, if not set explicitly. `pap-sha256` sends
+            // password in plain text, so it is not adopted without TLS, otherwise
+            // spoofed server could downgrade authentication
+            let auth_method = match (params.auth_method, id_response.auth_method) {
+                (Some(x), _) => x,
+                (None, Some(AuthMethod::PapSha256)) if !connector.is_tls() => {
+                    warn!(
+                        "Server advertised 'pap-sha256' auth method without TLS, using 'chap-sha1'"
+                    );
+                    AuthMethod::ChapSha1
+                }
+                (None, x) => x.unwrap_or_default(),
+            };
             Self::auth(
                 &mut read_stream,
                 &mut write_stream,
                 conn_data.next_sync(),
                 user,
-                password,
+                params.password.as_deref(),
                 &greeting.salt,
+                auth_method,
+            )
+            .await?;
+        }
+
+        if let Some(ref expr) = params.instance_check {
+            let passed = Self::check_instance(
+                &mut read_stream,
+                &mut write_stream,
+                conn_data.next_sync(),
+                expr,
             )
             .await?;
+            if !passed {
+                debug!("Instance {} did not pass check", connector);
+                return Err(Error::InstanceCheckFailed {
+                    address: connector.to_string(),
+                });
+            }
+        }
+
+        let server_info = Arc::new(ServerInfo::new(&greeting, &params.id, &id_response));
+
+        // Register key for graceful shutdown and all keys, watched before reconnect
+        let keys = std::iter::once(SHUTDOWN_EVENT_KEY.to_string())
+            .chain(
+                watchers
+                    .keys()
+                    .into_iter()
+                    .filter(|x| x != SHUTDOWN_EVENT_KEY),
+            )
+            .filter(|_| server_info.is_enabled(ProtocolFeature::Watchers));
+        for key in keys {
+            if let Some(request) = conn_data.prepare_request_without_response(Watch::new(&key)) {
+                trace!("Registering watcher for key '{}'", key);
+                write_stream.send(request).await?;
+            }
         }
 
         // TODO: review size of this queue
         // Make this queue slightly larger than queue between Client and Dispatcher
         let (writer_tx, writer_rx) =
-            mpsc::channel(internal_simultaneous_requests_threshold / 100 * 105);
+            mpsc::channel(params.internal_simultaneous_requests_threshold / 100 * 105);
         let writer_task_cancellation_token = CancellationToken::new();
-        let writer_task_handle = tokio::spawn(writer_task(
+        let (writer_task_result_tx, writer_task_result_rx) = oneshot::channel();
+        let writer_fut = writer_task(
             writer_rx,
             write_stream,
             writer_task_cancellation_token.clone(),
-        ));
+        );
+        spawner.spawn(Box::pin(async move {
+            let _ = writer_task_result_tx.send(writer_fut.await);
+        }));
 
         let this = Self {
             read_stream,
             writer_tx,
-            writer_task_handle,
+            writer_task_result_rx,
             writer_task_cancellation_token,
             data: conn_data,
+            watchers,
+            greeting,
+            server_info,
+            peer_addr,
+            heartbeat: params
+                .heartbeat_interval
+                .map(|interval| (interval, params.heartbeat_timeout)),
         };
 
         Ok(this)
     }
 
     pub(super) async fn new<A>(
-        addr: A,
-        user: Option<&str>,
-        password: Option<&str>,
-        timeout: Option<Duration>,
-        internal_simultaneous_requests_threshold: usize,
+        connector: &Connector<A>,
+        params: &ConnectionParams,
+        watchers: Arc<Watchers>,
+        spawner: &dyn Spawner,
     ) -> Result<Self, Error>
     where
         A: ToSocketAddrs + Display,
     {
-        match timeout {
-            Some(dur) => tokio::time::timeout(
-                dur,
-                Self::new_inner(
-                    addr,
-                    user,
-                    password,
-                    internal_simultaneous_requests_threshold,
-                ),
-            )
-            .await
-            .map_err(|_| Error::ConnectTimeout)
-            .and_then(|x| x),
-            None => {
-                Self::new_inner(
-                    addr,
-                    user,
-                    password,
-                    internal_simultaneous_requests_threshold,
-                )
+        let fut = Self::new_inner(connector, params, watchers, spawner);
+        match params.connect_timeout {
+            Some(dur) => tokio::time::timeout(dur, fut)
                 .await
+                .map_err(|_| Error::ConnectTimeout)
+                .and_then(|x| x),
+            None => fut.await,
+        }
+    }
+
+    /// Send request during handshake (before connection started to process
+    /// requests from client) and wait for response to it.
+    async fn handshake_request<R: Request>(
+        read_stream: &mut FramedRead<ReadHalf, ClientCodec>,
+        write_stream: &mut FramedWrite<WriteHalf, ClientCodec>,
+        sync: u32,
+        body: R,
+    ) -> Result<std::result::Result<Value, ErrorResponse>, Error> {
+        let request_type = R::request_type();
+        let mut request = EncodedRequest::new(body, None)?;
+        *request.sync_mut() = sync;
+
+        trace!("Sending {:?} request", request_type);
+        write_stream.send(request).await?;
+
+        // Skip events, since only keys, registered on this connection,
+        // can be sent by server, and their values will be sent again
+        // after registering them again
+        loop {
+            match Self::get_next_stream_value(read_stream).await? {
+                IncomingMessage::Response(Response {
+                    body: ResponseBody::Ok(x),
+                    ..
+                }) => return Ok(Ok(x)),
+                IncomingMessage::Response(Response {
+                    body: ResponseBody::Error(err),
+                    ..
+                }) => return Ok(Err(err)),
+                IncomingMessage::Response(Response {
+                    body: ResponseBody::Chunk(_),
+                    ..
+                }) => {
+                    return Err(Error::Other(anyhow::anyhow!(
+                        "Unexpected out-of-band response received in response to {:?} request",
+                        request_type
+                    )))
+                }
+                IncomingMessage::Event(x) => {
+                    trace!("Skipping event for key '{}' during handshake", x.key);
+                }
             }
         }
     }
 
+    /// Send ID request ([docs](https://www.tarantool.io/en/doc/latest/dev_guide/internals/box_protocol/#iproto-id-0x49)).
+    async fn id(
+        read_stream: &mut FramedRead<ReadHalf, ClientCodec>,
+        write_stream: &mut FramedWrite<WriteHalf, ClientCodec>,
+        sync: u32,
+        id: &Id,
+    ) -> Result<IdResponse, Error> {
+        debug!(
+            "Requesting protocol version {} with features {:?}",
+            id.protocol_version, id.features
+        );
+        let body = Self::handshake_request(read_stream, write_stream, sync, id.clone()).await??;
+        let response = IdResponse::decode(body)?;
+        debug!(
+            "Server protocol version: {}, features: {:?}, auth method: {:?}",
+            response.protocol_version, response.features, response.auth_method
+        );
+        Ok(response)
+    }
+
     async fn auth(
-        read_stream: &mut FramedRead<OwnedReadHalf, ClientCodec>,
-        write_stream: &mut FramedWrite<OwnedWriteHalf, ClientCodec>,
+        read_stream: &mut FramedRead<ReadHalf, ClientCodec>,
+        write_stream: &mut FramedWrite<WriteHalf, ClientCodec>,
         sync: u32,
         user: &str,
         password: Option<&str>,
         salt: &[u8],
+        method: AuthMethod,
     ) -> Result<(), Error> {
-        let mut request = EncodedRequest::new(Auth::new(user, password, salt), None).unwrap();
-        *request.sync_mut() = sync;
-
-        trace!("Sending auth request");
-        write_stream.send(request).await?;
+        trace!("Authenticating as '{}' using {}", user, method.name());
+        Self::handshake_request(
+            read_stream,
+            write_stream,
+            sync,
+            Auth::new(user, password, salt, method),
+        )
+        .await?
+        .map(drop)
+        .map_err(Error::Auth)
+    }
 
-        let resp = Self::get_next_stream_value(read_stream).await?;
-        match resp.body {
-            ResponseBody::Ok(_x) => Ok(()),
-            ResponseBody::Error(err) => Err(Error::Auth(err)),
-        }
+    /// Evaluate instance check and return whether it returned `true`.
+    async fn check_instance(
+        read_stream: &mut FramedRead<ReadHalf, ClientCodec>,
+        write_stream: &mut FramedWrite<WriteHalf, ClientCodec>,
+        sync: u32,
+        expr: &str,
+    ) -> Result<bool, Error> {
+        trace!("Checking instance with '{}'", expr);
+        let body =
+            Self::handshake_request(read_stream, write_stream, sync, Eval::new(expr, ())).await??;
+        Ok(matches!(
+            extract_iproto_data(body)?,
+            Value::Array(x) if x.first() == Some(&Value::Boolean(true))
+        ))
     }
 
     #[inline]
     async fn get_next_stream_value(
-        read_stream: &mut FramedRead<OwnedReadHalf, ClientCodec>,
-    ) -> Result<Response, ConnectionError> {
+        read_stream: &mut FramedRead<ReadHalf, ClientCodec>,
+    ) -> Result<IncomingMessage, ConnectionError> {
         match read_stream.try_next().await {
             Ok(Some(x)) => Ok(x),
             Ok(None) => Err(ConnectionError::ConnectionClosed),
@@ -309,45 +567,223 @@ impl Connection {
             response.sync,
             response.schema_version
         );
-        connection_data.respond_to_client(response.sync, Ok(response));
+        if connection_data.heartbeat_sync == Some(response.sync) {
+            trace!("Received response to heartbeat");
+            connection_data.heartbeat_sync = None;
+        } else if let ResponseBody::Chunk(value) = response.body {
+            connection_data.push_to_client(response.sync, value);
+        } else {
+            connection_data.respond_to_client(response.sync, Ok(response));
+        }
     }
 
-    /// Run connection until it breaks of `rx` is closed.
+    /// Pass event to subscribers and prepare acknowledgement, if key is still watched.
+    ///
+    /// If server is shutting down, no acknowledgements sent, since connection is
+    /// going to be closed anyway.
+    #[inline]
+    fn handle_event(
+        connection_data: &mut ConnectionData,
+        watchers: &Watchers,
+        event: Event,
+        shutting_down: bool,
+    ) -> Option<EncodedRequest> {
+        trace!("Received event for key '{}'", event.key);
+        let is_shutdown_key = event.key == SHUTDOWN_EVENT_KEY;
+        let is_watched = watchers.notify(&event.key, event.data);
+        if (is_watched || is_shutdown_key) && !shutting_down {
+            connection_data.prepare_request_without_response(Watch::new(&event.key))
+        } else {
+            None
+        }
+    }
+
+    /// Prepare WATCH or UNWATCH request, if it is still relevant.
+    #[inline]
+    fn handle_watcher_command(
+        connection_data: &mut ConnectionData,
+        watchers: &Watchers,
+        command: WatcherCommand,
+    ) -> Option<EncodedRequest> {
+        match command {
+            WatcherCommand::Watch(key) if watchers.is_watched(&key) => {
+                connection_data.prepare_request_without_response(Watch::new(&key))
+            }
+            // Key could be subscribed again while command was waiting in queue
+            WatcherCommand::Unwatch(key)
+                if watchers.take_unwatched(&key) && key != SHUTDOWN_EVENT_KEY =>
+            {
+                connection_data.prepare_request_without_response(Unwatch::new(&key))
+            }
+            _ => None,
+        }
+    }
+
+    /// Greeting, received from server.
+    pub(crate) fn greeting(&self) -> &Greeting {
+        &self.greeting
+    }
+
+    /// Address of server, if connected over TCP.
+    pub(crate) fn peer_addr(&self) -> Option<SocketAddr> {
+        self.peer_addr
+    }
+
+    /// Information about server, received during handshake.
+    pub(crate) fn server_info(&self) -> &Arc<ServerInfo> {
+        &self.server_info
+    }
+
+    /// Run connection until it breaks or `rx` is closed.
     ///
     /// `Ok` means `rx` was closed and connection should not be restarted.
     /// `Err` means connection was dropped due to some error.
-    pub(crate) async fn run(
+    pub(crate) async fn run<S>(
         self,
-        client_rx: &mut ReceiverStream<DispatcherRequest>,
-    ) -> Result<(), ()> {
+        client_rx: &mut S,
+        watcher_commands_rx: &mut mpsc::UnboundedReceiver<WatcherCommand>,
+        close_rx: &mut mpsc::UnboundedReceiver<CloseRequest>,
+        abandoned_requests: &Notify,
+    ) -> Result<Stopped, ConnectionError>
+    where
+        S: Stream<Item = DispatcherRequest> + Unpin,
+    {
         let Self {
             mut read_stream,
             writer_tx,
-            writer_task_handle,
+            writer_task_result_rx,
             writer_task_cancellation_token,
             mut data,
+            watchers,
+            heartbeat,
+            ..
         } = self;
 
         let mut not_sent_requests = Vec::new();
+        // Internal requests, which take priority over requests from client
+        let mut internal_requests = VecDeque::new();
+        // Set when server notified about its shutdown. In this state no new requests
+        // are sent and connection closed as soon as all in-flights are processed
+        let mut shutting_down = false;
+        // Set when connection is closed by user. Same as shutting down, no new requests
+        // are sent, but in-flights are awaited only until deadline
+        let mut closing = None;
+        let close_deadline = Fuse::terminated();
+        pin!(close_deadline);
+
+        // Heartbeat timer. When it fires, connection either checks whether anything was received
+        // since last check, or sends ping, or, if ping was already sent and nothing received
+        // since, considers connection dead
+        let heartbeat_timer = sleep(heartbeat.map(|x| x.0).unwrap_or_default());
+        pin!(heartbeat_timer);
+        let mut received_since_heartbeat = false;
 
         let send_to_writer_future = Fuse::terminated();
         pin!(send_to_writer_future);
 
         let result = loop {
+            if data.in_flights.is_empty() {
+                if let Some(request) = closing.take() {
+                    debug!("All in-flight requests processed, closing connection");
+                    break Ok(Stopped::Closed {
+                        request,
+                        abandoned: 0,
+                    });
+                }
+                if shutting_down {
+                    debug!(
+                        "All in-flight requests processed, closing connection to restarting server"
+                    );
+                    break Err(ConnectionError::ConnectionClosed);
+                }
+            }
+
+            if send_to_writer_future.is_terminated() {
+                if let Some(request) = internal_requests.pop_front() {
+                    send_to_writer_future.set(writer_tx.send((request, None)).fuse());
+                }
+            }
+
             tokio::select! {
                 // Read value from TCP stream
                 next = Connection::get_next_stream_value(&mut read_stream) => {
+                    received_since_heartbeat = true;
                     match next {
-                        Ok(x) => Connection::handle_response(&mut data, x),
+                        Ok(IncomingMessage::Response(x)) => Connection::handle_response(&mut data, x),
+                        Ok(IncomingMessage::Event(x)) => {
+                            if x.key == SHUTDOWN_EVENT_KEY && x.data == Value::Boolean(true) && !shutting_down {
+                                warn!(
+                                    "Server is shutting down, waiting for {} in-flight requests before reconnecting",
+                                    data.in_flights.len()
+                                );
+                                shutting_down = true;
+                            }
+                            if let Some(ack) = Connection::handle_event(&mut data, &watchers, x, shutting_down) {
+                                internal_requests.push_back(ack);
+                            }
+                        }
                         Err(err) => break Err(err),
                     }
                 }
 
+                // Stop sending new requests and wait for in-flights
+                Some(request) = close_rx.recv(), if closing.is_none() => {
+                    debug!(
+                        "Closing connection, waiting for {} in-flight requests",
+                        data.in_flights.len()
+                    );
+                    close_deadline.set(sleep(request.timeout).fuse());
+                    closing = Some(request);
+                }
+
+                // Abandon remaining in-flights
+                _ = &mut close_deadline, if closing.is_some() => {
+                    if let Some(request) = closing.take() {
+                        let abandoned = data.in_flights.len();
+                        warn!("Timeout while closing connection, abandoning {} in-flight requests", abandoned);
+                        break Ok(Stopped::Closed { request, abandoned });
+                    }
+                }
+
+                // Check if connection is alive
+                _ = &mut heartbeat_timer, if heartbeat.is_some() => {
+                    let Some((interval, timeout)) = heartbeat else {
+                        continue;
+                    };
+                    if received_since_heartbeat {
+                        received_since_heartbeat = false;
+                        heartbeat_timer.as_mut().reset(Instant::now() + interval);
+                    } else if data.heartbeat_sync.is_some() {
+                        warn!("No response to heartbeat in {:?}, connection considered dead", timeout);
+                        break Err(io::Error::new(io::ErrorKind::TimedOut, "Heartbeat timeout").into());
+                    } else {
+                        trace!("No messages received in {:?}, sending heartbeat", interval);
+                        if let Some(request) = data.prepare_heartbeat() {
+                            internal_requests.push_back(request);
+                        }
+                        heartbeat_timer.as_mut().reset(Instant::now() + timeout);
+                    }
+                }
+
+                // Forget requests, which are no longer awaited by clients
+                _ = abandoned_requests.notified() => {
+                    data.in_flights.retain(|_, tx| !tx.is_closed());
+                }
+
+                // Subscribe or unsubscribe from keys
+                Some(command) = watcher_commands_rx.recv() => {
+                    if let Some(request) = Connection::handle_watcher_command(&mut data, &watchers, command) {
+                        internal_requests.push_back(request);
+                    }
+                }
+
                 // Read value from internal queue if nothing being sent to writer
-                next = client_rx.next(), if send_to_writer_future.is_terminated() => {
+                // and server is not shutting down
+                next = client_rx.next(), if send_to_writer_future.is_terminated() && !shutting_down && closing.is_none() => {
                     if let Some((mut request, tx)) = next {
                         // If failed to prepare request or client already
                         // dropped oneshot - just go to next
+                        let send_state = tx.send_state().clone();
                         if tx.is_closed() || data
                             .try_prepare_request(&mut request, tx)
                             .is_err()
@@ -355,11 +791,11 @@ impl Connection {
                             continue;
                         }
 
-                        send_to_writer_future.set(writer_tx.send(request).fuse());
+                        send_to_writer_future.set(writer_tx.send((request, Some(send_state))).fuse());
                     } else {
                         // TODO: actually don't quit until all in-flights processed
                         debug!("All senders dropped");
-                        break Ok(());
+                        break Ok(Stopped::SendersDropped);
                     }
                 }
 
@@ -368,7 +804,7 @@ impl Connection {
                 send_res = &mut send_to_writer_future, if !send_to_writer_future.is_terminated() => {
                     // Error means writer rx is closed and connection should be terminated.
                     if let Err(err) = send_res {
-                        not_sent_requests.push(err.0);
+                        not_sent_requests.push(err.0 .0);
                         break Err(ConnectionError::ConnectionClosed)
                     }
                     // TODO: somehow return EncodedRequest from Err variant, so it can be retried
@@ -382,9 +818,9 @@ impl Connection {
         writer_task_cancellation_token.cancel();
 
         // Wait for writer task to finish
-        match writer_task_handle.await {
-            Err(err) => {
-                error!("Failed to await writer task's handle: {err}");
+        match writer_task_result_rx.await {
+            Err(_) => {
+                error!("Writer task stopped without sending result");
             }
             Ok((result, not_sent_requests_from_writer)) => {
                 not_sent_requests.extend(not_sent_requests_from_writer);
@@ -398,14 +834,15 @@ impl Connection {
         // Respond to all in flights with error
         data.send_error_to_all_in_flights(
             result
-                .clone()
+                .as_ref()
                 .err()
+                .cloned()
                 .unwrap_or(ConnectionError::ConnectionClosed),
         );
 
         // Schedule all not sent requests to resend
         data.return_requests_to_be_resent(not_sent_requests);
 
-        result.map_err(drop)
+        result
     }
 }
@@ -1,17 +1,37 @@
-use std::{fmt::Display, future::Future, pin::Pin, time::Duration};
+use std::{
+    fmt::Display,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU8, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
 use backoff::{backoff::Backoff, ExponentialBackoff, ExponentialBackoffBuilder};
+use futures::{future, StreamExt};
+use rmpv::Value;
 use tokio::{
     net::ToSocketAddrs,
-    sync::{mpsc, oneshot},
+    sync::{mpsc, oneshot, watch, Notify},
 };
 use tokio_stream::wrappers::ReceiverStream;
 use tracing::{debug, error};
 
-use super::connection::Connection;
+use super::{
+    connection::{CloseRequest, Connection, ConnectionParams, Stopped},
+    hooks::{Hooks, SetupRequests},
+    server_info::ServerInfo,
+    spawner::Spawner,
+    state::ConnectionState,
+    stream::Connector,
+    watchers::{WatcherCommand, WatcherSubscription, Watchers},
+};
 use crate::{
     codec::{request::EncodedRequest, response::Response},
-    Error, ReconnectInterval,
+    errors::ConnectionError,
+    Error, ReconnectInterval, RequestPolicy,
 };
 
 // Arc here is necessary to send same error to all waiting in-flights
@@ -43,8 +63,66 @@ impl From<EncodedRequest> for DispatcherResponse {
     }
 }
 
-#[repr(transparent)]
-pub(crate) struct DispatcherResponseSender(oneshot::Sender<DispatcherResponse>);
+/// State of sending request, shared between client and writer task.
+///
+/// Allows client to know, whether request was written to socket, and
+/// prevents writing requests, which are no longer awaited by client.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SendState(Arc<AtomicU8>);
+
+impl SendState {
+    const NOT_SENT: u8 = 0;
+    const SENT: u8 = 1;
+    const CANCELLED: u8 = 2;
+
+    /// Mark request as sent. Return `false` if request was cancelled.
+    pub(crate) fn try_mark_sent(&self) -> bool {
+        self.0
+            .compare_exchange(
+                Self::NOT_SENT,
+                Self::SENT,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            )
+            .is_ok()
+    }
+
+    /// Cancel request, if it is not sent yet.
+    fn cancel(&self) {
+        let _ = self.0.compare_exchange(
+            Self::NOT_SENT,
+            Self::CANCELLED,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        );
+    }
+
+    fn is_sent(&self) -> bool {
+        self.0.load(Ordering::Acquire) == Self::SENT
+    }
+}
+
+/// Cancels request and notifies connection, if client stopped awaiting response.
+struct AbandonGuard<'a> {
+    send_state: &'a SendState,
+    abandoned_requests: &'a Notify,
+    armed: bool,
+}
+
+impl Drop for AbandonGuard<'_> {
+    fn drop(&mut self) {
+        if self.armed {
+            self.send_state.cancel();
+            self.abandoned_requests.notify_one();
+        }
+    }
+}
+
+pub(crate) struct DispatcherResponseSender {
+    tx: oneshot::Sender<DispatcherResponse>,
+    push_tx: Option<mpsc::UnboundedSender<Value>>,
+    send_state: SendState,
+}
 
 impl DispatcherResponseSender {
     #[inline]
@@ -52,31 +130,212 @@ impl DispatcherResponseSender {
         self,
         value: impl Into<DispatcherResponse>,
     ) -> Result<(), DispatcherResponse> {
-        self.0.send(value.into())
+        self.tx.send(value.into())
+    }
+
+    /// Pass out-of-band value to client.
+    ///
+    /// `Err` means that client is not interested in such values.
+    #[inline]
+    pub(crate) fn push(&self, value: Value) -> Result<(), Value> {
+        match self.push_tx {
+            Some(ref push_tx) => push_tx.send(value).map_err(|err| err.0),
+            None => Err(value),
+        }
     }
 
     #[inline]
     pub(crate) fn is_closed(&self) -> bool {
-        self.0.is_closed()
+        self.tx.is_closed()
+    }
+
+    #[inline]
+    pub(crate) fn send_state(&self) -> &SendState {
+        &self.send_state
     }
 }
 
 pub(crate) struct DispatcherSender {
     tx: mpsc::Sender<DispatcherRequest>,
+    watchers: Arc<Watchers>,
+    state_rx: watch::Receiver<ConnectionState>,
+    server_info_rx: watch::Receiver<Option<Arc<ServerInfo>>>,
+    request_policy: RequestPolicy,
+    close_tx: mpsc::UnboundedSender<CloseRequest>,
+    abandoned_requests: Arc<Notify>,
 }
 
 impl DispatcherSender {
-    pub(crate) async fn send(&self, request: EncodedRequest) -> Result<Response, Error> {
+    /// Close connection and stop dispatcher, waiting for in-flight requests
+    /// no longer than `timeout`.
+    ///
+    /// Return number of abandoned in-flight requests.
+    pub(crate) async fn close(&self, timeout: Duration) -> usize {
+        let (tx, rx) = oneshot::channel();
+        if self.close_tx.send(CloseRequest { timeout, tx }).is_err() {
+            // Dispatcher already stopped
+            return 0;
+        }
+        rx.await.unwrap_or(0)
+    }
+
+    /// Current state of connection.
+    pub(crate) fn state(&self) -> ConnectionState {
+        self.state_rx.borrow().clone()
+    }
+
+    /// Receiver of connection state changes.
+    pub(crate) fn state_changes(&self) -> watch::Receiver<ConnectionState> {
+        self.state_rx.clone()
+    }
+
+    /// Information about server from last established connection.
+    pub(crate) fn server_info(&self) -> Option<Arc<ServerInfo>> {
+        self.server_info_rx.borrow().clone()
+    }
+
+    /// Subscribe to changes of the key.
+    pub(crate) fn watch(
+        &self,
+        key: String,
+    ) -> (WatcherSubscription, mpsc::UnboundedReceiver<Value>) {
+        self.watchers.subscribe(key)
+    }
+
+    pub(crate) async fn send(
+        &self,
+        request: EncodedRequest,
+        timeout: Option<Duration>,
+    ) -> Result<Response, Error> {
+        self.send_inner(request, None, timeout).await
+    }
+
+    /// Send request, passing all out-of-band responses for it into `push_tx`.
+    pub(crate) async fn send_with_push(
+        &self,
+        request: EncodedRequest,
+        push_tx: mpsc::UnboundedSender<Value>,
+        timeout: Option<Duration>,
+    ) -> Result<Response, Error> {
+        self.send_inner(request, Some(push_tx), timeout).await
+    }
+
+    async fn send_inner(
+        &self,
+        request: EncodedRequest,
+        push_tx: Option<mpsc::UnboundedSender<Value>>,
+        timeout: Option<Duration>,
+    ) -> Result<Response, Error> {
+        let send_state = SendState::default();
+        let fut = self.send_with_policy(request, push_tx, &send_state);
+        match timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, fut).await {
+                Ok(res) => res,
+                Err(_) => Err(Error::Timeout {
+                    sent: send_state.is_sent(),
+                }),
+            },
+            None => fut.await,
+        }
+    }
+
+    async fn send_with_policy(
+        &self,
+        request: EncodedRequest,
+        push_tx: Option<mpsc::UnboundedSender<Value>>,
+        send_state: &SendState,
+    ) -> Result<Response, Error> {
+        let fut = self.send_to_dispatcher(request, push_tx, send_state);
+        // Response, which is already received, takes priority over
+        // disconnect, which could happen right after it
+        match self.request_policy {
+            RequestPolicy::Queue => fut.await,
+            RequestPolicy::FailFast => tokio::select! {
+                biased;
+                res = fut => res,
+                _ = self.wait_disconnected() => Err(Error::NotConnected),
+            },
+            RequestPolicy::QueueWithDeadline(deadline) => tokio::select! {
+                biased;
+                res = fut => res,
+                _ = self.wait_disconnected_for(deadline) => Err(Error::NotConnected),
+            },
+        }
+    }
+
+    /// Wait until connection is being (re)established.
+    ///
+    /// Never finishes if dispatcher is stopped, because in this case
+    /// request will fail anyway.
+    async fn wait_disconnected(&self) {
+        let mut state_rx = self.state_rx.clone();
+        let dispatcher_stopped = state_rx
+            .wait_for(ConnectionState::is_connecting)
+            .await
+            .is_err();
+        if dispatcher_stopped {
+            future::pending::<()>().await;
+        }
+    }
+
+    /// Wait until connection is being (re)established for `duration` in a row.
+    async fn wait_disconnected_for(&self, duration: Duration) {
+        let mut state_rx = self.state_rx.clone();
+        loop {
+            self.wait_disconnected().await;
+            let res = tokio::time::timeout(duration, state_rx.wait_for(|x| !x.is_connecting()))
+                .await
+                .map(|x| x.is_ok());
+            match res {
+                Ok(true) => continue,
+                Ok(false) => future::pending::<()>().await,
+                Err(_) => return,
+            }
+        }
+    }
+
+    /// Send request to dispatcher and wait for response.
+    ///
+    /// If this future is dropped before response is received, request is
+    /// cancelled and connection is notified to forget about it.
+    async fn send_to_dispatcher(
+        &self,
+        request: EncodedRequest,
+        push_tx: Option<mpsc::UnboundedSender<Value>>,
+        send_state: &SendState,
+    ) -> Result<Response, Error> {
+        let mut guard = AbandonGuard {
+            send_state,
+            abandoned_requests: &self.abandoned_requests,
+            armed: true,
+        };
+        let res = self
+            .send_to_dispatcher_inner(request, push_tx, send_state)
+            .await;
+        guard.armed = false;
+        res
+    }
+
+    async fn send_to_dispatcher_inner(
+        &self,
+        request: EncodedRequest,
+        push_tx: Option<mpsc::UnboundedSender<Value>>,
+        send_state: &SendState,
+    ) -> Result<Response, Error> {
         let mut request = Some(request);
         loop {
             let (tx, rx) = oneshot::channel();
-            let tx = DispatcherResponseSender(tx);
+            let tx = DispatcherResponseSender {
+                tx,
+                push_tx: push_tx.clone(),
+                send_state: send_state.clone(),
+            };
 
             // SAFETY: initial value is put in Option immediately.
             // On next iterations value is put in Option right before `continue` expression.
-            if let Err(send_err) = self.tx.send((request.take().unwrap(), tx)).await {
-                request = Some(send_err.0 .0);
-                continue;
+            // Error means that dispatcher is stopped and request will never be processed.
+            if self.tx.send((request.take().unwrap(), tx)).await.is_err() {
+                return Err(Error::ConnectionClosed);
             }
 
             match rx.await {
@@ -91,6 +350,14 @@ impl DispatcherSender {
     }
 }
 
+/// Parameters of reconnection.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ReconnectParams {
+    pub interval: Option<ReconnectInterval>,
+    pub max_attempts: Option<u32>,
+    pub max_duration: Option<Duration>,
+}
+
 type ConnectDynFuture = dyn Future<Output = Result<Connection, Error>> + Send;
 
 /// Dispatching messages from client to connection.
@@ -100,89 +367,296 @@ pub(crate) struct Dispatcher {
     rx: ReceiverStream<DispatcherRequest>,
     conn: Option<Connection>,
     conn_factory: Box<dyn Fn() -> Pin<Box<ConnectDynFuture>> + Send + Sync>,
-    reconnect_interval: Option<ReconnectInterval>,
+    reconnect: ReconnectParams,
+    request_policy: RequestPolicy,
+    watchers: Arc<Watchers>,
+    watcher_commands_rx: mpsc::UnboundedReceiver<WatcherCommand>,
+    hooks: Hooks,
+    spawner: Arc<dyn Spawner>,
+    internal_simultaneous_requests_threshold: usize,
+    state_tx: watch::Sender<ConnectionState>,
+    server_info_tx: watch::Sender<Option<Arc<ServerInfo>>>,
+    close_tx: mpsc::UnboundedSender<CloseRequest>,
+    close_rx: mpsc::UnboundedReceiver<CloseRequest>,
+    abandoned_requests: Arc<Notify>,
+}
+
+/// Reason why reconnection was stopped.
+enum ReconnectStopped {
+    /// Limits exceeded after specified number of failed attempts.
+    GaveUp(u32),
+    /// Connection closed by user.
+    Closed(CloseRequest),
+}
+
+/// Wait until deadline of reconnection. Never finishes if there is no deadline.
+async fn wait_deadline(deadline: Option<Instant>) {
+    match deadline {
+        Some(x) => tokio::time::sleep_until(x.into()).await,
+        None => future::pending().await,
+    }
 }
 
 impl Dispatcher {
-    pub(crate) async fn prepare<A>(
-        addr: A,
-        user: Option<&str>,
-        password: Option<&str>,
-        connect_timeout: Option<Duration>,
-        reconnect_interval: Option<ReconnectInterval>,
-        internal_simultaneous_requests_threshold: usize,
-    ) -> Result<(impl Future<Output = ()>, DispatcherSender), Error>
+    /// Create dispatcher without connection.
+    ///
+    /// Connection can be established with [`Dispatcher::connect`], otherwise it is
+    /// established in background after start, same way as reconnection.
+    ///
+    /// Each connection attempt is made to next connector from `connectors`
+    /// in round-robin manner. `connectors` should not be empty.
+    pub(crate) fn new<A>(
+        connectors: Vec<Connector<A>>,
+        params: ConnectionParams,
+        reconnect: ReconnectParams,
+        request_policy: RequestPolicy,
+        hooks: Hooks,
+        spawner: Arc<dyn Spawner>,
+    ) -> (Self, DispatcherSender)
     where
         A: ToSocketAddrs + Display + Clone + Send + Sync + 'static,
     {
-        let user: Option<String> = user.map(Into::into);
-        let password: Option<String> = password.map(Into::into);
+        let internal_simultaneous_requests_threshold =
+            params.internal_simultaneous_requests_threshold;
+        let (watchers, watcher_commands_rx) = Watchers::new();
+        let factory_watchers = watchers.clone();
+        let factory_spawner = spawner.clone();
+        let next_connector = AtomicUsize::new(0);
         let conn_factory = Box::new(move || {
-            let addr = addr.clone();
-            let user = user.clone();
-            let password = password.clone();
-            let connect_timeout = connect_timeout;
+            let idx = next_connector.fetch_add(1, Ordering::Relaxed) % connectors.len();
+            let connector = connectors[idx].clone();
+            let params = params.clone();
+            let watchers = factory_watchers.clone();
+            let spawner = factory_spawner.clone();
             Box::pin(async move {
-                Connection::new(
-                    addr,
-                    user.as_deref(),
-                    password.as_deref(),
-                    connect_timeout,
-                    internal_simultaneous_requests_threshold,
-                )
-                .await
+                Connection::new(&connector, &params, watchers, spawner.as_ref()).await
             }) as Pin<Box<ConnectDynFuture>>
         });
 
-        let conn = conn_factory().await?;
+        let (state_tx, state_rx) = watch::channel(ConnectionState::Connecting);
+        let (server_info_tx, server_info_rx) = watch::channel(None);
 
         let (tx, rx) = mpsc::channel(internal_simultaneous_requests_threshold);
+        let (close_tx, close_rx) = mpsc::unbounded_channel();
+        let abandoned_requests = Arc::new(Notify::new());
 
-        Ok((
+        (
             Self {
                 rx: ReceiverStream::new(rx),
-                conn: Some(conn),
+                conn: None,
                 conn_factory,
-                reconnect_interval,
-            }
-            .run(),
-            DispatcherSender { tx },
-        ))
+                reconnect,
+                request_policy,
+                watchers: watchers.clone(),
+                watcher_commands_rx,
+                hooks,
+                spawner,
+                internal_simultaneous_requests_threshold,
+                state_tx,
+                server_info_tx,
+                close_tx: close_tx.clone(),
+                close_rx,
+                abandoned_requests: abandoned_requests.clone(),
+            },
+            DispatcherSender {
+                tx,
+                watchers,
+                state_rx,
+                server_info_rx,
+                request_policy,
+                close_tx,
+                abandoned_requests,
+            },
+        )
+    }
+
+    /// Establish first connection.
+    pub(crate) async fn connect(&mut self) -> Result<(), Error> {
+        let conn = (self.conn_factory)().await?;
+        self.set_connection(conn);
+        Ok(())
+    }
+
+    /// Set new established connection and update state.
+    fn set_connection(&mut self, conn: Connection) {
+        let _ = self
+            .state_tx
+            .send_replace(ConnectionState::connected(&conn));
+        let _ = self
+            .server_info_tx
+            .send_replace(Some(conn.server_info().clone()));
+        self.conn = Some(conn);
     }
 
-    async fn reconnect(&mut self) {
+    /// Try to reconnect until success, until limits are exceeded
+    /// or until connection is closed.
+    async fn reconnect(&mut self) -> Result<(), ReconnectStopped> {
         let mut reconn_int_state = self
-            .reconnect_interval
+            .reconnect
+            .interval
             .as_ref()
             .map(ReconnectIntervalState::from);
+        // Checked while connecting too, since connection attempt may hang
+        // (e.g. if `connect_timeout` is not set)
+        let deadline = self.reconnect.max_duration.map(|x| Instant::now() + x);
+        let mut failed_attempts = 0;
         loop {
-            match (self.conn_factory)().await {
+            let res = tokio::select! {
+                res = (self.conn_factory)() => res,
+                Some(request) = self.close_rx.recv() => return Err(ReconnectStopped::Closed(request)),
+                _ = wait_deadline(deadline) => {
+                    error!("Failed to reconnect to Tarantool: reconnection deadline exceeded");
+                    return Err(ReconnectStopped::GaveUp(failed_attempts + 1));
+                }
+            };
+            match res {
                 Ok(conn) => {
-                    self.conn = Some(conn);
-                    return;
+                    self.set_connection(conn);
+                    return Ok(());
                 }
                 Err(err) => {
+                    failed_attempts += 1;
                     error!("Failed to reconnect to Tarantool: {:#}", err);
+                    let err = Arc::new(err);
+                    if let Some(ref hook) = self.hooks.on_reconnect_failed {
+                        self.spawner.spawn(hook(err.clone()));
+                    }
+                    let attempts_exceeded = self
+                        .reconnect
+                        .max_attempts
+                        .is_some_and(|x| failed_attempts >= x);
+                    let duration_exceeded = deadline.is_some_and(|x| Instant::now() >= x);
+                    if attempts_exceeded || duration_exceeded {
+                        return Err(ReconnectStopped::GaveUp(failed_attempts));
+                    }
+                    // Attempts are counted same way after disconnect and for
+                    // first connection (where first attempt is `Connecting`)
+                    let _ = self.state_tx.send_replace(ConnectionState::Reconnecting {
+                        attempt: failed_attempts + 1,
+                        last_error: err,
+                    });
                     if let Some(ref mut x) = reconn_int_state {
-                        tokio::time::sleep(x.next_timeout()).await;
+                        let mut wake_at = Instant::now() + x.next_timeout();
+                        if let Some(deadline) = deadline {
+                            wake_at = wake_at.min(deadline);
+                        }
+                        tokio::select! {
+                            _ = tokio::time::sleep_until(wake_at.into()) => {},
+                            Some(request) = self.close_rx.recv() => return Err(ReconnectStopped::Closed(request)),
+                        }
+                        if deadline.is_some_and(|x| Instant::now() >= x) {
+                            return Err(ReconnectStopped::GaveUp(failed_attempts));
+                        }
                     }
                 }
             }
         }
     }
 
+    /// Run connection until it is closed.
+    ///
+    /// If `on_connect` hook is set, requests from clients are passed to connection
+    /// only after hook is finished.
+    async fn run_connection(&mut self, conn: Connection) -> Result<Stopped, ConnectionError> {
+        let Some(ref on_connect) = self.hooks.on_connect else {
+            return conn
+                .run(
+                    &mut self.rx,
+                    &mut self.watcher_commands_rx,
+                    &mut self.close_rx,
+                    &self.abandoned_requests,
+                )
+                .await;
+        };
+        let (tx, rx) = mpsc::channel(self.internal_simultaneous_requests_threshold);
+        let sender = DispatcherSender {
+            tx,
+            watchers: self.watchers.clone(),
+            state_rx: self.state_tx.subscribe(),
+            server_info_rx: self.server_info_tx.subscribe(),
+            request_policy: self.request_policy,
+            close_tx: self.close_tx.clone(),
+            abandoned_requests: self.abandoned_requests.clone(),
+        };
+        let hook = on_connect(sender, conn.greeting().clone());
+        let mut requests = SetupRequests::new(hook, rx).chain(&mut self.rx);
+        conn.run(
+            &mut requests,
+            &mut self.watcher_commands_rx,
+            &mut self.close_rx,
+            &self.abandoned_requests,
+        )
+        .await
+    }
+
     pub(crate) async fn run(mut self) {
         debug!("Starting dispatcher");
         loop {
             if let Some(conn) = self.conn.take() {
-                if conn.run(&mut self.rx).await.is_ok() {
-                    return;
+                match self.run_connection(conn).await {
+                    Ok(Stopped::SendersDropped) => {
+                        self.watchers.close();
+                        let _ = self.state_tx.send_replace(ConnectionState::Closed);
+                        return;
+                    }
+                    Ok(Stopped::Closed { request, abandoned }) => {
+                        return self.finish_closing(request, abandoned);
+                    }
+                    Err(err) => {
+                        let err = Arc::new(Error::from(err));
+                        let _ = self.state_tx.send_replace(ConnectionState::Reconnecting {
+                            attempt: 1,
+                            last_error: err.clone(),
+                        });
+                        if let Some(ref hook) = self.hooks.on_disconnect {
+                            self.spawner.spawn(hook(err));
+                        }
+                    }
                 }
             } else {
-                self.reconnect().await;
+                match self.reconnect().await {
+                    Ok(()) => {}
+                    Err(ReconnectStopped::GaveUp(attempts)) => {
+                        error!("Giving up reconnecting to Tarantool after {attempts} attempts");
+                        self.watchers.close();
+                        let _ = self.state_tx.send_replace(ConnectionState::Closed);
+                        return self.reject_requests(attempts).await;
+                    }
+                    Err(ReconnectStopped::Closed(request)) => {
+                        return self.finish_closing(request, 0);
+                    }
+                }
             }
         }
     }
+
+    /// Respond with error to all pending and new requests until all senders are dropped
+    /// or connection is closed.
+    async fn reject_requests(mut self, attempts: u32) {
+        loop {
+            tokio::select! {
+                next = self.rx.next() => match next {
+                    Some((_, tx)) => {
+                        let _ = tx.send(Error::ReconnectFailed { attempts });
+                    }
+                    None => return,
+                },
+                Some(request) = self.close_rx.recv() => return self.finish_closing(request, 0),
+            }
+        }
+    }
+
+    /// Stop dispatcher and notify closing side.
+    ///
+    /// All requests, which are not yet processed by dispatcher, are dropped and
+    /// will fail with `ConnectionClosed` error.
+    fn finish_closing(self, request: CloseRequest, abandoned: usize) {
+        debug!("Dispatcher stopped by user, {abandoned} requests abandoned");
+        self.watchers.close();
+        let _ = self.state_tx.send_replace(ConnectionState::Closed);
+        drop(self);
+        let _ = request.tx.send(abandoned);
+    }
 }
 
 /// Get interval before next reconnect attempt.
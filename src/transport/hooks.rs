@@ -0,0 +1,119 @@
+use std::{
+    fmt,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::{future::BoxFuture, FutureExt, Stream};
+use tokio::sync::mpsc;
+use tracing::error;
+
+use super::dispatcher::{DispatcherRequest, DispatcherSender};
+use crate::{codec::Greeting, Error};
+
+/// Callback, invoked after connection is established.
+///
+/// `C` is a handle, through which hook can send requests to server.
+pub(crate) type ConnectHook<C> =
+    Arc<dyn Fn(C, Greeting) -> BoxFuture<'static, Result<(), Error>> + Send + Sync>;
+
+pub(crate) type ErrorHook = Arc<dyn Fn(Arc<Error>) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Callbacks, invoked by dispatcher on connection events.
+pub(crate) struct Hooks<C = DispatcherSender> {
+    pub on_connect: Option<ConnectHook<C>>,
+    pub on_disconnect: Option<ErrorHook>,
+    pub on_reconnect_failed: Option<ErrorHook>,
+}
+
+impl<C: 'static> Hooks<C> {
+    /// Convert hooks to accept other type of handle in `on_connect` hook.
+    pub(crate) fn map_connection<T>(&self, f: impl Fn(T) -> C + Send + Sync + 'static) -> Hooks<T> {
+        let on_connect = self
+            .on_connect
+            .clone()
+            .map(|hook| Arc::new(move |conn, greeting| hook(f(conn), greeting)) as ConnectHook<T>);
+        Hooks {
+            on_connect,
+            on_disconnect: self.on_disconnect.clone(),
+            on_reconnect_failed: self.on_reconnect_failed.clone(),
+        }
+    }
+}
+
+impl<C> Clone for Hooks<C> {
+    fn clone(&self) -> Self {
+        Self {
+            on_connect: self.on_connect.clone(),
+            on_disconnect: self.on_disconnect.clone(),
+            on_reconnect_failed: self.on_reconnect_failed.clone(),
+        }
+    }
+}
+
+impl<C> Default for Hooks<C> {
+    fn default() -> Self {
+        Self {
+            on_connect: None,
+            on_disconnect: None,
+            on_reconnect_failed: None,
+        }
+    }
+}
+
+impl<C> fmt::Debug for Hooks<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hooks")
+            .field("on_connect", &self.on_connect.is_some())
+            .field("on_disconnect", &self.on_disconnect.is_some())
+            .field("on_reconnect_failed", &self.on_reconnect_failed.is_some())
+            .finish()
+    }
+}
+
+/// Stream of requests, sent by `on_connect` hook.
+///
+/// Stream is finished when hook's future is finished, so requests from clients
+/// can be chained after it.
+pub(crate) struct SetupRequests {
+    hook: Option<BoxFuture<'static, Result<(), Error>>>,
+    rx: mpsc::Receiver<DispatcherRequest>,
+}
+
+impl SetupRequests {
+    pub(crate) fn new(
+        hook: BoxFuture<'static, Result<(), Error>>,
+        rx: mpsc::Receiver<DispatcherRequest>,
+    ) -> Self {
+        Self {
+            hook: Some(hook),
+            rx,
+        }
+    }
+}
+
+impl Stream for SetupRequests {
+    type Item = DispatcherRequest;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let Some(ref mut hook) = self.hook else {
+            return Poll::Ready(None);
+        };
+        if let Poll::Ready(result) = hook.poll_unpin(cx) {
+            if let Err(err) = result {
+                error!("on_connect hook failed: {:#}", err);
+            }
+            // Requests, which are not yet received, are dropped
+            // and senders get `ConnectionClosed` error
+            self.hook = None;
+            self.rx.close();
+            return Poll::Ready(None);
+        }
+        match self.rx.poll_recv(cx) {
+            Poll::Ready(Some(x)) => Poll::Ready(Some(x)),
+            // All senders dropped, but hook is still running
+            Poll::Ready(None) | Poll::Pending => Poll::Pending,
+        }
+    }
+}
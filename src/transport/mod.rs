@@ -1,4 +1,31 @@
-pub(crate) use self::dispatcher::{Dispatcher, DispatcherSender};
+#[cfg(feature = "tls")]
+pub use self::tls::TlsConfig;
+pub(crate) use self::{
+    connection::ConnectionParams,
+    dispatcher::{Dispatcher, DispatcherSender, ReconnectParams},
+    hooks::Hooks,
+    socket::SocketOptions,
+    stream::{Connector, Endpoint},
+    watchers::WatcherSubscription,
+};
+pub use self::{
+    proxy::ProxyConfig,
+    resolver::Resolver,
+    server_info::{ProtocolFeature, ServerInfo, TarantoolVersion},
+    spawner::Spawner,
+    state::ConnectionState,
+};
 
 mod connection;
 mod dispatcher;
+mod hooks;
+mod proxy;
+mod resolver;
+mod server_info;
+mod socket;
+mod spawner;
+mod state;
+mod stream;
+#[cfg(feature = "tls")]
+mod tls;
+mod watchers;
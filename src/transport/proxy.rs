@@ -0,0 +1,323 @@
+use std::{fmt, io, net::IpAddr};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+const SOCKS5_VERSION: u8 = 0x05;
+const SOCKS5_AUTH_NONE: u8 = 0x00;
+const SOCKS5_AUTH_PASSWORD: u8 = 0x02;
+const SOCKS5_AUTH_NO_ACCEPTABLE: u8 = 0xff;
+const SOCKS5_CMD_CONNECT: u8 = 0x01;
+const SOCKS5_ATYP_IPV4: u8 = 0x01;
+const SOCKS5_ATYP_DOMAIN: u8 = 0x03;
+const SOCKS5_ATYP_IPV6: u8 = 0x04;
+
+/// Maximum size of HTTP response headers from proxy.
+const HTTP_MAX_RESPONSE_SIZE: usize = 8 * 1024;
+
+/// Proxy, through which connections to Tarantool are tunneled.
+///
+/// Handshake with proxy is performed right after TCP connection to proxy is
+/// established, before TLS handshake and reading greeting. Address of Tarantool
+/// is passed to proxy as is, so hostnames are resolved by proxy.
+///
+/// # Example
+///
+/// ```rust,compile
+/// use tarantool_rs::{Connection, ProxyConfig};
+///
+/// # async fn async_wrapper() {
+/// let mut proxy = ProxyConfig::socks5("bastion.example.com:1080");
+/// proxy.credentials("user", "password");
+/// let connection = Connection::builder()
+///     .proxy(proxy)
+///     .build("tarantool.internal:3301")
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ProxyConfig {
+    kind: ProxyKind,
+    addr: String,
+    credentials: Option<(String, String)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ProxyKind {
+    Socks5,
+    HttpConnect,
+}
+
+impl ProxyConfig {
+    /// Connect through SOCKS5 proxy, listening on `addr`.
+    pub fn socks5(addr: impl Into<String>) -> Self {
+        Self::new(ProxyKind::Socks5, addr.into())
+    }
+
+    /// Connect through HTTP proxy, listening on `addr`, using `CONNECT` method.
+    pub fn http_connect(addr: impl Into<String>) -> Self {
+        Self::new(ProxyKind::HttpConnect, addr.into())
+    }
+
+    fn new(kind: ProxyKind, addr: String) -> Self {
+        Self {
+            kind,
+            addr,
+            credentials: None,
+        }
+    }
+
+    /// Sets username and password for authentication on proxy.
+    ///
+    /// Username/password authentication is used for SOCKS5 and Basic
+    /// authentication for HTTP. By default no authentication is performed.
+    pub fn credentials(
+        &mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> &mut Self {
+        self.credentials = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Address of proxy.
+    pub(crate) fn addr(&self) -> &str {
+        &self.addr
+    }
+
+    /// Ask proxy, connected with `stream`, to open tunnel to `target` (`host:port`).
+    pub(crate) async fn handshake<S>(&self, stream: &mut S, target: &str) -> io::Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let (host, port) = split_host_port(target)?;
+        match self.kind {
+            ProxyKind::Socks5 => self.socks5_handshake(stream, host, port).await,
+            ProxyKind::HttpConnect => self.http_connect_handshake(stream, host, port).await,
+        }
+    }
+
+    async fn socks5_handshake<S>(&self, stream: &mut S, host: &str, port: u16) -> io::Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        // Negotiate authentication method
+        let methods: &[u8] = if self.credentials.is_some() {
+            &[SOCKS5_AUTH_NONE, SOCKS5_AUTH_PASSWORD]
+        } else {
+            &[SOCKS5_AUTH_NONE]
+        };
+        let mut buf = vec![SOCKS5_VERSION, methods.len() as u8];
+        buf.extend_from_slice(methods);
+        stream.write_all(&buf).await?;
+        let mut reply = [0; 2];
+        stream.read_exact(&mut reply).await?;
+        check_socks5_version(reply[0])?;
+        match (reply[1], &self.credentials) {
+            (SOCKS5_AUTH_NONE, _) => {}
+            (SOCKS5_AUTH_PASSWORD, Some((username, password))) => {
+                // RFC 1929
+                let mut buf = vec![0x01];
+                buf.push(field_len(username, "username")?);
+                buf.extend_from_slice(username.as_bytes());
+                buf.push(field_len(password, "password")?);
+                buf.extend_from_slice(password.as_bytes());
+                stream.write_all(&buf).await?;
+                stream.read_exact(&mut reply).await?;
+                if reply[1] != 0x00 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::PermissionDenied,
+                        "SOCKS5 proxy authentication failed",
+                    ));
+                }
+            }
+            (SOCKS5_AUTH_NO_ACCEPTABLE, _) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    "SOCKS5 proxy rejected all authentication methods",
+                ))
+            }
+            (method, _) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("SOCKS5 proxy selected unexpected authentication method {method}"),
+                ))
+            }
+        }
+
+        // Request tunnel
+        let mut buf = vec![SOCKS5_VERSION, SOCKS5_CMD_CONNECT, 0x00];
+        match host.parse::<IpAddr>() {
+            Ok(IpAddr::V4(ip)) => {
+                buf.push(SOCKS5_ATYP_IPV4);
+                buf.extend_from_slice(&ip.octets());
+            }
+            Ok(IpAddr::V6(ip)) => {
+                buf.push(SOCKS5_ATYP_IPV6);
+                buf.extend_from_slice(&ip.octets());
+            }
+            Err(_) => {
+                buf.push(SOCKS5_ATYP_DOMAIN);
+                buf.push(field_len(host, "hostname")?);
+                buf.extend_from_slice(host.as_bytes());
+            }
+        }
+        buf.extend_from_slice(&port.to_be_bytes());
+        stream.write_all(&buf).await?;
+
+        let mut reply = [0; 4];
+        stream.read_exact(&mut reply).await?;
+        check_socks5_version(reply[0])?;
+        if reply[1] != 0x00 {
+            return Err(io::Error::new(
+                io::ErrorKind::ConnectionRefused,
+                format!(
+                    "SOCKS5 proxy failed to connect: {}",
+                    socks5_reply_message(reply[1])
+                ),
+            ));
+        }
+        // Skip bound address
+        let addr_len = match reply[3] {
+            SOCKS5_ATYP_IPV4 => 4,
+            SOCKS5_ATYP_IPV6 => 16,
+            SOCKS5_ATYP_DOMAIN => stream.read_u8().await?.into(),
+            x => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("SOCKS5 proxy returned unknown address type {x}"),
+                ))
+            }
+        };
+        let mut bound_addr = vec![0; addr_len + 2];
+        stream.read_exact(&mut bound_addr).await?;
+        Ok(())
+    }
+
+    async fn http_connect_handshake<S>(
+        &self,
+        stream: &mut S,
+        host: &str,
+        port: u16,
+    ) -> io::Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let authority = if host.contains(':') {
+            format!("[{host}]:{port}")
+        } else {
+            format!("{host}:{port}")
+        };
+        let mut request = format!("CONNECT {authority} HTTP/1.1\r\nHost: {authority}\r\n");
+        if let Some((ref username, ref password)) = self.credentials {
+            let token = STANDARD.encode(format!("{username}:{password}"));
+            request.push_str(&format!("Proxy-Authorization: Basic {token}\r\n"));
+        }
+        request.push_str("\r\n");
+        stream.write_all(request.as_bytes()).await?;
+
+        // Read byte by byte to not consume greeting, which follows response
+        let mut response = Vec::new();
+        while !response.ends_with(b"\r\n\r\n") {
+            if response.len() >= HTTP_MAX_RESPONSE_SIZE {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "HTTP proxy response is too large",
+                ));
+            }
+            response.push(stream.read_u8().await?);
+        }
+        let status_line = String::from_utf8_lossy(&response);
+        let status_line = status_line.lines().next().unwrap_or_default();
+        match status_line.split_whitespace().nth(1) {
+            Some(code) if code.starts_with('2') => Ok(()),
+            Some(_) => Err(io::Error::new(
+                io::ErrorKind::ConnectionRefused,
+                format!("HTTP proxy failed to connect: {status_line}"),
+            )),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid HTTP proxy response: {status_line}"),
+            )),
+        }
+    }
+}
+
+impl fmt::Debug for ProxyConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyConfig")
+            .field("kind", &self.kind)
+            .field("addr", &self.addr)
+            .field(
+                "username",
+                &self.credentials.as_ref().map(|(username, _)| username),
+            )
+            .finish()
+    }
+}
+
+/// Split address like `localhost:3301` or `[::1]:3301` into host and port.
+fn split_host_port(addr: &str) -> io::Result<(&str, u16)> {
+    addr.rsplit_once(':')
+        .and_then(|(host, port)| {
+            let host = host.trim_start_matches('[').trim_end_matches(']');
+            Some((host, port.parse().ok()?))
+        })
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Address '{addr}' should be in 'host:port' format"),
+            )
+        })
+}
+
+fn check_socks5_version(version: u8) -> io::Result<()> {
+    if version == SOCKS5_VERSION {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Unexpected SOCKS version {version} in proxy response"),
+        ))
+    }
+}
+
+fn field_len(value: &str, name: &str) -> io::Result<u8> {
+    value.len().try_into().map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("SOCKS5 {name} is longer than 255 bytes"),
+        )
+    })
+}
+
+fn socks5_reply_message(code: u8) -> &'static str {
+    match code {
+        0x01 => "general SOCKS server failure",
+        0x02 => "connection not allowed by ruleset",
+        0x03 => "network unreachable",
+        0x04 => "host unreachable",
+        0x05 => "connection refused",
+        0x06 => "TTL expired",
+        0x07 => "command not supported",
+        0x08 => "address type not supported",
+        _ => "unknown error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_host_port_parses_addresses() {
+        assert_eq!(
+            split_host_port("localhost:3301").unwrap(),
+            ("localhost", 3301)
+        );
+        assert_eq!(split_host_port("[::1]:3301").unwrap(), ("::1", 3301));
+        assert!(split_host_port("localhost").is_err());
+        assert!(split_host_port("localhost:port").is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use std::{fmt, future::Future, io, net::SocketAddr};
+
+use futures::{future::BoxFuture, FutureExt};
+
+/// Resolver of addresses of Tarantool instances into socket addresses.
+///
+/// Resolver is called on each connection attempt, so changes of addresses
+/// (for example, after restart of pod in Kubernetes) are picked up on reconnect.
+/// All returned addresses are tried in order until connection succeeded.
+///
+/// By default addresses are resolved with system resolver. Custom resolver
+/// allows service discovery systems to supply addresses directly.
+///
+/// # Example
+///
+/// ```rust,compile
+/// use std::net::SocketAddr;
+///
+/// use tarantool_rs::Connection;
+///
+/// # async fn async_wrapper() {
+/// let connection = Connection::builder()
+///     .resolver(|_addr: &str| async {
+///         // Get addresses from service discovery
+///         Ok(vec![SocketAddr::from(([127, 0, 0, 1], 3301))])
+///     })
+///     .build("tarantool")
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+pub trait Resolver: Send + Sync + 'static {
+    /// Resolve address, passed to builder, into socket addresses.
+    fn resolve(&self, addr: &str) -> BoxFuture<'static, io::Result<Vec<SocketAddr>>>;
+}
+
+impl<F, Fut> Resolver for F
+where
+    F: Fn(&str) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = io::Result<Vec<SocketAddr>>> + Send + 'static,
+{
+    fn resolve(&self, addr: &str) -> BoxFuture<'static, io::Result<Vec<SocketAddr>>> {
+        self(addr).boxed()
+    }
+}
+
+impl fmt::Debug for dyn Resolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Resolver")
+    }
+}
@@ -0,0 +1,255 @@
+use std::fmt;
+
+use crate::{
+    codec::{consts::AuthMethod, request::Id, response::IdResponse, Greeting},
+    Error,
+};
+
+/// Version of Tarantool, parsed from greeting message.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TarantoolVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    /// Short hash of commit, if present in version string (like `2.10.0-beta2-91-g08c9b4963`).
+    pub commit: Option<String>,
+}
+
+impl TarantoolVersion {
+    /// Parse version string like `2.11.1` or `2.10.0-beta2-91-g08c9b4963`.
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let (core, suffix) = value.split_once('-').unwrap_or((value, ""));
+        let mut parts = core.splitn(3, '.').map(str::parse::<u32>);
+        let major = parts.next()?.ok()?;
+        let minor = parts.next()?.ok()?;
+        let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+        let commit = suffix
+            .rsplit('-')
+            .next()
+            .and_then(|x| x.strip_prefix('g'))
+            .filter(|x| !x.is_empty() && x.chars().all(|c| c.is_ascii_hexdigit()))
+            .map(Into::into);
+        Some(Self {
+            major,
+            minor,
+            patch,
+            commit,
+        })
+    }
+
+    /// Parse version from greeting like `Tarantool 2.11.1 (Binary) <uuid>`.
+    pub(crate) fn from_greeting(greeting: &Greeting) -> Option<Self> {
+        greeting
+            .server
+            .split_whitespace()
+            .nth(1)
+            .and_then(Self::parse)
+    }
+
+    /// Whether this version supports ID request (added in 2.10).
+    pub(crate) fn supports_id_request(&self) -> bool {
+        (self.major, self.minor) >= (2, 10)
+    }
+}
+
+impl fmt::Display for TarantoolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(ref commit) = self.commit {
+            write!(f, " ({})", commit)?;
+        }
+        Ok(())
+    }
+}
+
+/// Feature of IPROTO protocol ([docs](https://www.tarantool.io/en/doc/latest/reference/reference_lua/box_iproto/feature/)).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProtocolFeature {
+    Streams,
+    Transactions,
+    ErrorExtension,
+    Watchers,
+    Pagination,
+    SpaceAndIndexNames,
+    WatchOnce,
+    /// Tuples in responses to DML requests are sent as `MP_TUPLE` extension.
+    DmlTupleExtension,
+    /// Tuples, returned from `CALL`, are sent as `MP_TUPLE` extension.
+    CallReturnTupleExtension,
+    /// Tuples in arguments of `CALL` are accepted as `MP_TUPLE` extension.
+    CallArgTupleExtension,
+    /// Feature, unknown to this crate.
+    Other(u64),
+}
+
+impl ProtocolFeature {
+    pub(crate) fn from_id(id: u64) -> Self {
+        match id {
+            0 => Self::Streams,
+            1 => Self::Transactions,
+            2 => Self::ErrorExtension,
+            3 => Self::Watchers,
+            4 => Self::Pagination,
+            5 => Self::SpaceAndIndexNames,
+            6 => Self::WatchOnce,
+            7 => Self::DmlTupleExtension,
+            8 => Self::CallReturnTupleExtension,
+            9 => Self::CallArgTupleExtension,
+            x => Self::Other(x),
+        }
+    }
+
+    pub(crate) fn id(&self) -> u64 {
+        match self {
+            Self::Streams => 0,
+            Self::Transactions => 1,
+            Self::ErrorExtension => 2,
+            Self::Watchers => 3,
+            Self::Pagination => 4,
+            Self::SpaceAndIndexNames => 5,
+            Self::WatchOnce => 6,
+            Self::DmlTupleExtension => 7,
+            Self::CallReturnTupleExtension => 8,
+            Self::CallArgTupleExtension => 9,
+            Self::Other(x) => *x,
+        }
+    }
+}
+
+/// Information about server, received while establishing connection.
+#[derive(Clone, Debug)]
+pub struct ServerInfo {
+    /// Version of Tarantool. `None` if it can't be parsed from greeting.
+    pub version: Option<TarantoolVersion>,
+    /// UUID of instance from greeting.
+    pub uuid: Option<String>,
+    /// Protocol version, negotiated with server.
+    pub protocol_version: u64,
+    /// Features, supported by server.
+    pub features: Vec<ProtocolFeature>,
+    /// Features, both requested by client and supported by server.
+    pub negotiated_features: Vec<ProtocolFeature>,
+    /// Authentication method, set on server (`box.cfg.auth_type`).
+    pub auth_method: Option<AuthMethod>,
+}
+
+impl ServerInfo {
+    pub(crate) fn new(greeting: &Greeting, requested: &Id, id: &IdResponse) -> Self {
+        // Greeting looks like 'Tarantool 2.11.1 (Binary) 8ad2b5a8-5b4b-4b65-9e0e-1b4fbb5b0d4b'
+        let version = TarantoolVersion::from_greeting(greeting);
+        let uuid = greeting.server.split_whitespace().nth(3).map(Into::into);
+        let features: Vec<_> = id
+            .features
+            .iter()
+            .copied()
+            .map(ProtocolFeature::from_id)
+            .collect();
+        let negotiated_features = requested
+            .features
+            .iter()
+            .filter(|x| features.contains(x))
+            .copied()
+            .collect();
+        Self {
+            version,
+            uuid,
+            protocol_version: id.protocol_version.min(requested.protocol_version.into()),
+            features,
+            negotiated_features,
+            auth_method: id.auth_method,
+        }
+    }
+
+    /// Whether feature is supported by server.
+    pub fn supports(&self, feature: ProtocolFeature) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// Whether feature is requested by client and supported by server.
+    pub fn is_enabled(&self, feature: ProtocolFeature) -> bool {
+        self.negotiated_features.contains(&feature)
+    }
+
+    /// Return error if feature is not enabled for connection.
+    pub(crate) fn require(&self, feature: ProtocolFeature) -> Result<(), Error> {
+        if self.is_enabled(feature) {
+            Ok(())
+        } else {
+            Err(Error::UnsupportedFeature(feature))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version() {
+        assert_eq!(
+            TarantoolVersion::parse("2.11.1"),
+            Some(TarantoolVersion {
+                major: 2,
+                minor: 11,
+                patch: 1,
+                commit: None
+            })
+        );
+        assert_eq!(
+            TarantoolVersion::parse("2.10.0-beta2-91-g08c9b4963"),
+            Some(TarantoolVersion {
+                major: 2,
+                minor: 10,
+                patch: 0,
+                commit: Some("08c9b4963".into())
+            })
+        );
+        assert_eq!(TarantoolVersion::parse("unknown"), None);
+
+        assert!(!TarantoolVersion::parse("1.10.15")
+            .unwrap()
+            .supports_id_request());
+        assert!(!TarantoolVersion::parse("2.8.4")
+            .unwrap()
+            .supports_id_request());
+        assert!(TarantoolVersion::parse("2.10.0")
+            .unwrap()
+            .supports_id_request());
+        assert!(TarantoolVersion::parse("3.0.1")
+            .unwrap()
+            .supports_id_request());
+    }
+
+    #[test]
+    fn server_info_from_greeting() {
+        let greeting = Greeting {
+            server: "Tarantool 2.11.1 (Binary) 8ad2b5a8-5b4b-4b65-9e0e-1b4fbb5b0d4b".into(),
+            salt: Vec::new(),
+        };
+        let id = IdResponse {
+            protocol_version: 4,
+            features: vec![0, 1, 3, 42],
+            auth_method: None,
+        };
+        let info = ServerInfo::new(&greeting, &Id::default(), &id);
+        assert_eq!(info.version.as_ref().map(|x| x.minor), Some(11));
+        assert_eq!(
+            info.uuid.as_deref(),
+            Some("8ad2b5a8-5b4b-4b65-9e0e-1b4fbb5b0d4b")
+        );
+        assert_eq!(info.protocol_version, 3);
+        assert!(info.supports(ProtocolFeature::Watchers));
+        assert!(!info.supports(ProtocolFeature::ErrorExtension));
+        assert!(info.supports(ProtocolFeature::Other(42)));
+        assert_eq!(
+            info.negotiated_features,
+            vec![
+                ProtocolFeature::Streams,
+                ProtocolFeature::Transactions,
+                ProtocolFeature::Watchers
+            ]
+        );
+        assert!(info.require(ProtocolFeature::Watchers).is_ok());
+        assert!(info.require(ProtocolFeature::Other(42)).is_err());
+    }
+}
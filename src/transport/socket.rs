@@ -0,0 +1,95 @@
+use std::{io, net::SocketAddr, time::Duration};
+
+use socket2::{Domain, Protocol, SockRef, Socket, TcpKeepalive, Type};
+use tokio::net::{TcpSocket, TcpStream};
+
+/// Options of TCP sockets, applied to each opened socket (including reconnects).
+///
+/// Unset options are left with OS defaults.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SocketOptions {
+    pub nodelay: bool,
+    pub keepalive_time: Option<Duration>,
+    pub keepalive_interval: Option<Duration>,
+    pub keepalive_retries: Option<u32>,
+    pub send_buffer_size: Option<usize>,
+    pub recv_buffer_size: Option<usize>,
+    pub local_addr: Option<SocketAddr>,
+    pub bind_device: Option<String>,
+}
+
+impl SocketOptions {
+    /// Open socket with these options and connect it to `addr`.
+    pub(crate) async fn connect(&self, addr: SocketAddr) -> io::Result<TcpStream> {
+        let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+        socket.set_nonblocking(true)?;
+        if let Some(size) = self.send_buffer_size {
+            socket.set_send_buffer_size(size)?;
+        }
+        if let Some(size) = self.recv_buffer_size {
+            socket.set_recv_buffer_size(size)?;
+        }
+        self.bind(&socket)?;
+        let stream = TcpSocket::from_std_stream(socket.into())
+            .connect(addr)
+            .await?;
+        self.apply_to_connected(&stream)?;
+        Ok(stream)
+    }
+
+    fn bind(&self, socket: &Socket) -> io::Result<()> {
+        #[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+        if let Some(ref device) = self.bind_device {
+            socket.bind_device(Some(device.as_bytes()))?;
+        }
+        #[cfg(not(any(target_os = "android", target_os = "fuchsia", target_os = "linux")))]
+        if self.bind_device.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "Binding to network interface is not supported on this platform",
+            ));
+        }
+        if let Some(addr) = self.local_addr {
+            socket.bind(&addr.into())?;
+        }
+        Ok(())
+    }
+
+    fn apply_to_connected(&self, stream: &TcpStream) -> io::Result<()> {
+        let socket = SockRef::from(stream);
+        if self.nodelay {
+            socket.set_tcp_nodelay(true)?;
+        }
+        if let Some(time) = self.keepalive_time {
+            #[allow(unused_mut)]
+            let mut keepalive = TcpKeepalive::new().with_time(time);
+            #[cfg(any(
+                target_os = "android",
+                target_os = "freebsd",
+                target_os = "fuchsia",
+                target_os = "ios",
+                target_os = "linux",
+                target_os = "macos",
+                target_os = "netbsd",
+                target_os = "windows",
+            ))]
+            if let Some(interval) = self.keepalive_interval {
+                keepalive = keepalive.with_interval(interval);
+            }
+            #[cfg(any(
+                target_os = "android",
+                target_os = "freebsd",
+                target_os = "fuchsia",
+                target_os = "ios",
+                target_os = "linux",
+                target_os = "macos",
+                target_os = "netbsd",
+            ))]
+            if let Some(retries) = self.keepalive_retries {
+                keepalive = keepalive.with_retries(retries);
+            }
+            socket.set_tcp_keepalive(&keepalive)?;
+        }
+        Ok(())
+    }
+}
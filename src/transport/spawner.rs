@@ -0,0 +1,53 @@
+use std::fmt;
+
+use futures::future::BoxFuture;
+
+/// Executor of background tasks of connection (dispatcher, writer and hooks).
+///
+/// Spawned tasks use Tokio's IO and timers, so they should be polled inside Tokio
+/// runtime context. This allows to run them on dedicated runtime, inside
+/// [`LocalSet`](tokio::task::LocalSet) or with custom executor.
+///
+/// By default tasks are spawned on runtime, in which connection is built.
+///
+/// # Example
+///
+/// ```rust,compile
+/// use tarantool_rs::Connection;
+///
+/// # async fn async_wrapper() {
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+/// let connection = Connection::builder()
+///     // Run all IO on dedicated runtime
+///     .spawner(runtime.handle().clone())
+///     .build("localhost:3301")
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+pub trait Spawner: Send + Sync + 'static {
+    /// Spawn task in background.
+    fn spawn(&self, future: BoxFuture<'static, ()>);
+}
+
+impl Spawner for tokio::runtime::Handle {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        // Task is detached, since connection tracks its state by itself
+        tokio::runtime::Handle::spawn(self, future);
+    }
+}
+
+impl<F> Spawner for F
+where
+    F: Fn(BoxFuture<'static, ()>) + Send + Sync + 'static,
+{
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        self(future)
+    }
+}
+
+impl fmt::Debug for dyn Spawner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Spawner")
+    }
+}
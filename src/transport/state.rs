@@ -0,0 +1,56 @@
+use std::{net::SocketAddr, sync::Arc, time::Instant};
+
+use super::connection::Connection;
+use crate::Error;
+
+/// State of connection to Tarantool.
+///
+/// Current state can be retrieved with [`Connection::state`](crate::Connection::state)
+/// and observed with [`Connection::state_changes`](crate::Connection::state_changes).
+#[derive(Clone, Debug)]
+pub enum ConnectionState {
+    /// First connection is being established.
+    Connecting,
+    /// Connection established.
+    Connected {
+        /// Server info from greeting message (version and instance UUID).
+        server: String,
+        /// Time when connection was established.
+        since: Instant,
+        /// Address of server, if connected over TCP.
+        peer_addr: Option<SocketAddr>,
+    },
+    /// Connection lost and new one is being established (or waiting for next attempt).
+    Reconnecting {
+        /// Number of current attempt, starting from 1.
+        ///
+        /// For first connection, established in background, first attempt
+        /// is reported as [`Connecting`](Self::Connecting), so numbering
+        /// continues from 2.
+        attempt: u32,
+        /// Error, which caused disconnect or failure of previous attempt.
+        last_error: Arc<Error>,
+    },
+    /// Connection closed and won't be reestablished.
+    Closed,
+}
+
+impl ConnectionState {
+    pub(crate) fn connected(conn: &Connection) -> Self {
+        Self::Connected {
+            server: conn.greeting().server.clone(),
+            since: Instant::now(),
+            peer_addr: conn.peer_addr(),
+        }
+    }
+
+    /// Whether connection is established.
+    pub fn is_connected(&self) -> bool {
+        matches!(self, Self::Connected { .. })
+    }
+
+    /// Whether connection is being established or reestablished.
+    pub fn is_connecting(&self) -> bool {
+        matches!(self, Self::Connecting | Self::Reconnecting { .. })
+    }
+}
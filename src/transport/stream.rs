@@ -0,0 +1,237 @@
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::{
+    fmt::{self, Display},
+    io,
+    net::SocketAddr,
+    sync::Arc,
+};
+
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::{TcpStream, ToSocketAddrs},
+};
+use tracing::debug;
+
+use super::{proxy::ProxyConfig, resolver::Resolver, socket::SocketOptions};
+
+#[cfg(feature = "tls")]
+use super::tls::TlsContext;
+
+/// Prefix of address, which points to unix domain socket.
+#[cfg(unix)]
+const UNIX_SOCKET_PREFIX: &str = "unix:";
+
+/// Reading half of connection stream.
+pub(crate) type ReadHalf = Box<dyn AsyncRead + Send + Unpin>;
+
+/// Writing half of connection stream.
+pub(crate) type WriteHalf = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// Address of Tarantool instance.
+#[derive(Clone)]
+pub(crate) enum Endpoint<A> {
+    Tcp(A),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl<A> Endpoint<A>
+where
+    A: ToSocketAddrs + Display,
+{
+    /// Parse address, treating addresses with `unix:` prefix as paths to unix sockets.
+    pub(crate) fn from_addr(addr: A) -> Self {
+        #[cfg(unix)]
+        if let Some(path) = addr.to_string().strip_prefix(UNIX_SOCKET_PREFIX) {
+            return Self::Unix(path.into());
+        }
+        Self::Tcp(addr)
+    }
+}
+
+/// Endpoint together with transport parameters, used to open new streams.
+#[derive(Clone)]
+pub(crate) struct Connector<A> {
+    endpoint: Endpoint<A>,
+    resolver: Option<Arc<dyn Resolver>>,
+    socket_options: SocketOptions,
+    proxy: Option<ProxyConfig>,
+    #[cfg(feature = "tls")]
+    tls: Option<TlsContext>,
+}
+
+impl<A> Connector<A>
+where
+    A: ToSocketAddrs + Display,
+{
+    pub(crate) fn new(endpoint: Endpoint<A>) -> Self {
+        Self {
+            endpoint,
+            resolver: None,
+            socket_options: SocketOptions::default(),
+            proxy: None,
+            #[cfg(feature = "tls")]
+            tls: None,
+        }
+    }
+
+    /// Resolve TCP addresses with custom resolver instead of system one.
+    pub(crate) fn with_resolver(mut self, resolver: Option<Arc<dyn Resolver>>) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// Apply options to each opened TCP socket.
+    pub(crate) fn with_socket_options(mut self, socket_options: SocketOptions) -> Self {
+        self.socket_options = socket_options;
+        self
+    }
+
+    /// Tunnel TCP connections through proxy.
+    pub(crate) fn with_proxy(mut self, proxy: Option<ProxyConfig>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// Wrap all opened streams into TLS.
+    #[cfg(feature = "tls")]
+    pub(crate) fn with_tls(mut self, tls: Option<TlsContext>) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// Whether opened streams are wrapped into TLS.
+    pub(crate) fn is_tls(&self) -> bool {
+        #[cfg(feature = "tls")]
+        return self.tls.is_some();
+        #[cfg(not(feature = "tls"))]
+        false
+    }
+
+    /// Open stream to endpoint and split it into reading and writing halves.
+    ///
+    /// Address of TCP endpoint is resolved on each call and all resolved addresses
+    /// are tried in order. Address of connected peer (or proxy, if set) is returned
+    /// along with stream.
+    pub(crate) async fn connect(&self) -> io::Result<(ReadHalf, WriteHalf, Option<SocketAddr>)> {
+        match self.endpoint {
+            Endpoint::Tcp(ref addr) => {
+                let (stream, peer_addr) = match self.proxy {
+                    Some(ref proxy) => {
+                        let addrs = tokio::net::lookup_host(proxy.addr()).await?.collect();
+                        let (mut stream, peer_addr) = self.connect_tcp(proxy.addr(), addrs).await?;
+                        proxy.handshake(&mut stream, &addr.to_string()).await?;
+                        debug!("Tunnel to {} opened through proxy {}", addr, proxy.addr());
+                        (stream, peer_addr)
+                    }
+                    None => {
+                        let addrs = match self.resolver {
+                            Some(ref resolver) => resolver.resolve(&addr.to_string()).await?,
+                            None => tokio::net::lookup_host(addr).await?.collect(),
+                        };
+                        self.connect_tcp(addr, addrs).await?
+                    }
+                };
+                #[cfg(feature = "tls")]
+                if let Some(ref tls) = self.tls {
+                    let addr = addr.to_string();
+                    let (read, write) = tls.connect(stream, Some(host_from_addr(&addr))).await?;
+                    return Ok((read, write, Some(peer_addr)));
+                }
+                let (read, write) = stream.into_split();
+                Ok((Box::new(read), Box::new(write), Some(peer_addr)))
+            }
+            #[cfg(unix)]
+            Endpoint::Unix(ref path) => {
+                let stream = tokio::net::UnixStream::connect(path).await?;
+                #[cfg(feature = "tls")]
+                if let Some(ref tls) = self.tls {
+                    let (read, write) = tls.connect(stream, None).await?;
+                    return Ok((read, write, None));
+                }
+                let (read, write) = stream.into_split();
+                Ok((Box::new(read), Box::new(write), None))
+            }
+        }
+    }
+
+    /// Connect to first available of addresses, resolved from `addr`.
+    async fn connect_tcp(
+        &self,
+        addr: &(impl Display + ?Sized),
+        addrs: Vec<SocketAddr>,
+    ) -> io::Result<(TcpStream, SocketAddr)> {
+        debug!("Address {} resolved to {:?}", addr, addrs);
+
+        let mut last_err = None;
+        for peer_addr in addrs {
+            match self.socket_options.connect(peer_addr).await {
+                Ok(stream) => return Ok((stream, peer_addr)),
+                Err(err) => {
+                    debug!("Failed to connect to {}: {}", peer_addr, err);
+                    last_err = Some(err);
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Address {} resolved to empty list", addr),
+            )
+        }))
+    }
+}
+
+impl<A: Display> Display for Connector<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.endpoint.fmt(f)
+    }
+}
+
+/// Extract host from `host:port` address, removing brackets around IPv6 addresses.
+#[cfg(feature = "tls")]
+fn host_from_addr(addr: &str) -> &str {
+    let host = addr.rsplit_once(':').map_or(addr, |(host, _)| host);
+    host.trim_start_matches('[').trim_end_matches(']')
+}
+
+impl<A: Display> Display for Endpoint<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => addr.fmt(f),
+            #[cfg(unix)]
+            Self::Unix(path) => write!(f, "{}{}", UNIX_SOCKET_PREFIX, path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tcp_endpoint_from_addr() {
+        let endpoint = Endpoint::from_addr("localhost:3301");
+        assert!(matches!(endpoint, Endpoint::Tcp("localhost:3301")));
+        assert_eq!(endpoint.to_string(), "localhost:3301");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_endpoint_from_addr() {
+        let endpoint = Endpoint::from_addr("unix:/var/run/tarantool.sock");
+        assert!(
+            matches!(endpoint, Endpoint::Unix(ref x) if x == std::path::Path::new("/var/run/tarantool.sock"))
+        );
+        assert_eq!(endpoint.to_string(), "unix:/var/run/tarantool.sock");
+    }
+
+    #[cfg(feature = "tls")]
+    #[test]
+    fn host_from_addr_strips_port() {
+        assert_eq!(host_from_addr("localhost:3301"), "localhost");
+        assert_eq!(host_from_addr("[::1]:3301"), "::1");
+        assert_eq!(host_from_addr("localhost"), "localhost");
+    }
+}
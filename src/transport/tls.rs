@@ -0,0 +1,187 @@
+use std::{fmt, io, sync::Arc};
+
+use anyhow::{anyhow, Context};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::{
+    rustls::{
+        pki_types::{CertificateDer, ServerName},
+        ClientConfig, RootCertStore,
+    },
+    TlsConnector,
+};
+
+use super::stream::{ReadHalf, WriteHalf};
+use crate::Error;
+
+/// Parameters of TLS connection to Tarantool (available with `tls` feature).
+///
+/// Tarantool Enterprise accept encrypted connections when listening with
+/// `transport=ssl` ([docs](https://www.tarantool.io/en/enterprise_doc/security/#traffic-encryption)).
+///
+/// # Example
+///
+/// ```rust,compile
+/// use tarantool_rs::{Connection, TlsConfig};
+///
+/// # async fn async_wrapper() {
+/// let mut tls = TlsConfig::new();
+/// tls.ca_certificates_pem(std::fs::read("ca.crt").unwrap())
+///     .client_certificate_pem(
+///         std::fs::read("client.crt").unwrap(),
+///         std::fs::read("client.key").unwrap(),
+///     );
+/// let connection = Connection::builder()
+///     .tls(tls)
+///     .build("tarantool.example.com:3301")
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct TlsConfig {
+    ca_certificates: Vec<Vec<u8>>,
+    client_certificate: Option<(Vec<u8>, Vec<u8>)>,
+    server_name: Option<String>,
+}
+
+impl TlsConfig {
+    /// Create empty TLS config.
+    ///
+    /// At least one CA certificate should be added before connecting.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add PEM-encoded CA certificates, used to verify server certificate.
+    ///
+    /// Can be called multiple times, all certificates are used together.
+    pub fn ca_certificates_pem(&mut self, pem: impl Into<Vec<u8>>) -> &mut Self {
+        self.ca_certificates.push(pem.into());
+        self
+    }
+
+    /// Sets PEM-encoded client certificate chain and private key for mutual TLS.
+    ///
+    /// By default client certificate is not sent.
+    pub fn client_certificate_pem(
+        &mut self,
+        certificate_chain: impl Into<Vec<u8>>,
+        private_key: impl Into<Vec<u8>>,
+    ) -> &mut Self {
+        self.client_certificate = Some((certificate_chain.into(), private_key.into()));
+        self
+    }
+
+    /// Sets name of server, used for SNI and for verifying server certificate.
+    ///
+    /// By default host part of address is used. Should be set explicitly when
+    /// connecting over unix domain socket.
+    pub fn server_name(&mut self, server_name: impl Into<Option<String>>) -> &mut Self {
+        self.server_name = server_name.into();
+        self
+    }
+
+    /// Parse certificates and keys and prepare connector.
+    pub(crate) fn prepare(&self) -> Result<TlsContext, Error> {
+        let mut roots = RootCertStore::empty();
+        for pem in &self.ca_certificates {
+            for cert in parse_certificates(pem)? {
+                roots
+                    .add(cert)
+                    .context("Invalid CA certificate")
+                    .map_err(Error::Other)?;
+            }
+        }
+        if roots.is_empty() {
+            return Err(Error::Other(anyhow!(
+                "At least one CA certificate required for TLS connection"
+            )));
+        }
+
+        let config = ClientConfig::builder().with_root_certificates(roots);
+        let config = match self.client_certificate {
+            Some((ref certificate_chain, ref private_key)) => {
+                let certificate_chain = parse_certificates(certificate_chain)?;
+                let private_key = rustls_pemfile::private_key(&mut private_key.as_slice())
+                    .context("Failed to parse client private key")
+                    .map_err(Error::Other)?
+                    .ok_or_else(|| Error::Other(anyhow!("Client private key not found")))?;
+                config
+                    .with_client_auth_cert(certificate_chain, private_key)
+                    .context("Invalid client certificate or private key")
+                    .map_err(Error::Other)?
+            }
+            None => config.with_no_client_auth(),
+        };
+
+        let server_name = self
+            .server_name
+            .as_deref()
+            .map(parse_server_name)
+            .transpose()?;
+
+        Ok(TlsContext {
+            connector: TlsConnector::from(Arc::new(config)),
+            server_name,
+        })
+    }
+}
+
+impl fmt::Debug for TlsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TlsConfig")
+            .field("ca_certificates", &self.ca_certificates.len())
+            .field("client_certificate", &self.client_certificate.is_some())
+            .field("server_name", &self.server_name)
+            .finish()
+    }
+}
+
+fn parse_certificates(pem: &[u8]) -> Result<Vec<CertificateDer<'static>>, Error> {
+    rustls_pemfile::certs(&mut &*pem)
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to parse PEM certificates")
+        .map_err(Error::Other)
+}
+
+fn parse_server_name(name: &str) -> Result<ServerName<'static>, Error> {
+    ServerName::try_from(name.to_owned())
+        .with_context(|| format!("Invalid TLS server name '{}'", name))
+        .map_err(Error::Other)
+}
+
+/// Prepared TLS parameters, shared between all reconnects.
+#[derive(Clone)]
+pub(crate) struct TlsContext {
+    connector: TlsConnector,
+    server_name: Option<ServerName<'static>>,
+}
+
+impl TlsContext {
+    /// Perform TLS handshake over established stream.
+    ///
+    /// `host` is used as server name, if it was not set explicitly.
+    pub(crate) async fn connect<S>(
+        &self,
+        stream: S,
+        host: Option<&str>,
+    ) -> io::Result<(ReadHalf, WriteHalf)>
+    where
+        S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    {
+        let server_name = match (&self.server_name, host) {
+            (Some(x), _) => x.clone(),
+            (None, Some(host)) => parse_server_name(host)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?,
+            (None, None) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "TLS server name is not set",
+                ))
+            }
+        };
+        let stream = self.connector.connect(server_name, stream).await?;
+        let (read, write) = tokio::io::split(stream);
+        Ok((Box::new(read), Box::new(write)))
+    }
+}
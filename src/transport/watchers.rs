@@ -0,0 +1,226 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use parking_lot::Mutex;
+use rmpv::Value;
+use tokio::sync::mpsc;
+use tracing::{debug, trace};
+
+/// Key, which is broadcasted by server when it is shutting down.
+///
+/// This key is always watched by connection, regardless of subscribers
+/// ([docs](https://www.tarantool.io/en/doc/latest/dev_guide/internals/iproto/graceful_shutdown/)).
+pub(crate) const SHUTDOWN_EVENT_KEY: &str = "box.shutdown";
+
+/// Command for connection to subscribe or unsubscribe from key on server.
+#[derive(Debug)]
+pub(crate) enum WatcherCommand {
+    Watch(String),
+    Unwatch(String),
+}
+
+/// Registry of all keys, watched through single dispatcher.
+///
+/// Registry outlives connections, so after reconnect all keys are registered again.
+pub(crate) struct Watchers {
+    state: Mutex<WatchersState>,
+    commands_tx: mpsc::UnboundedSender<WatcherCommand>,
+    next_subscriber_id: AtomicU64,
+}
+
+#[derive(Default)]
+struct WatchersState {
+    keys: HashMap<String, WatchedKey>,
+    closed: bool,
+}
+
+#[derive(Default)]
+struct WatchedKey {
+    subscribers: Vec<(u64, mpsc::UnboundedSender<Value>)>,
+    last_value: Option<Value>,
+    /// Last subscriber is gone, but UNWATCH is not sent yet, so key is still
+    /// watched on server and can be subscribed again without new WATCH.
+    unwatching: bool,
+}
+
+impl Watchers {
+    pub(crate) fn new() -> (Arc<Self>, mpsc::UnboundedReceiver<WatcherCommand>) {
+        let (commands_tx, commands_rx) = mpsc::unbounded_channel();
+        let this = Self {
+            state: Mutex::new(WatchersState::default()),
+            commands_tx,
+            next_subscriber_id: AtomicU64::new(0),
+        };
+        (Arc::new(this), commands_rx)
+    }
+
+    /// Add new subscriber for key.
+    ///
+    /// If key is already watched by someone else, last received value is
+    /// immediately passed to new subscriber, since server won't send it again.
+    pub(crate) fn subscribe(
+        self: &Arc<Self>,
+        key: String,
+    ) -> (WatcherSubscription, mpsc::UnboundedReceiver<Value>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let id = self.next_subscriber_id.fetch_add(1, Ordering::Relaxed);
+        {
+            let mut state = self.state.lock();
+            // If dispatcher is stopped, just drop sender, so stream is finished immediately
+            if !state.closed {
+                let is_new_key = !state.keys.contains_key(&key);
+                let entry = state.keys.entry(key.clone()).or_default();
+                if entry.unwatching {
+                    debug!("Key '{}' is subscribed again before unsubscribing", key);
+                    entry.unwatching = false;
+                }
+                if let Some(ref value) = entry.last_value {
+                    let _ = tx.send(value.clone());
+                }
+                entry.subscribers.push((id, tx));
+                if is_new_key {
+                    debug!("Subscribing to key '{}'", key);
+                    let _ = self.commands_tx.send(WatcherCommand::Watch(key.clone()));
+                }
+            }
+        }
+        let subscription = WatcherSubscription {
+            watchers: self.clone(),
+            key,
+            id,
+        };
+        (subscription, rx)
+    }
+
+    fn unsubscribe(&self, key: &str, id: u64) {
+        let mut state = self.state.lock();
+        let Some(entry) = state.keys.get_mut(key) else {
+            return;
+        };
+        entry.subscribers.retain(|(x, _)| *x != id);
+        if entry.subscribers.is_empty() {
+            debug!("No subscribers left for key '{}', unsubscribing", key);
+            // Key is removed only when UNWATCH is actually sent (check `take_unwatched`)
+            entry.unwatching = true;
+            let _ = self.commands_tx.send(WatcherCommand::Unwatch(key.into()));
+        }
+    }
+
+    pub(crate) fn is_watched(&self, key: &str) -> bool {
+        self.state
+            .lock()
+            .keys
+            .get(key)
+            .is_some_and(|x| !x.unwatching)
+    }
+
+    /// Remove key without subscribers before sending UNWATCH.
+    ///
+    /// Returns `false` if key was subscribed again (or already removed)
+    /// and UNWATCH should not be sent.
+    pub(crate) fn take_unwatched(&self, key: &str) -> bool {
+        let mut state = self.state.lock();
+        if state.keys.get(key).is_some_and(|x| x.unwatching) {
+            let _ = state.keys.remove(key);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// All currently watched keys.
+    pub(crate) fn keys(&self) -> Vec<String> {
+        self.state.lock().keys.keys().cloned().collect()
+    }
+
+    /// Pass new value of the key to all its subscribers.
+    ///
+    /// Returns `true`, if key is still watched and notification should be acknowledged.
+    pub(crate) fn notify(&self, key: &str, value: Value) -> bool {
+        let mut state = self.state.lock();
+        let Some(entry) = state.keys.get_mut(key) else {
+            trace!("Received event for key '{}', which is not watched", key);
+            return false;
+        };
+        entry
+            .subscribers
+            .retain(|(_, tx)| tx.send(value.clone()).is_ok());
+        entry.last_value = Some(value);
+        true
+    }
+
+    /// Drop all subscribers, finishing their streams.
+    pub(crate) fn close(&self) {
+        let mut state = self.state.lock();
+        state.closed = true;
+        state.keys.clear();
+    }
+}
+
+/// Handle of single subscriber, which unsubscribes from key on drop.
+pub(crate) struct WatcherSubscription {
+    watchers: Arc<Watchers>,
+    key: String,
+    id: u64,
+}
+
+impl WatcherSubscription {
+    pub(crate) fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl Drop for WatcherSubscription {
+    fn drop(&mut self) {
+        self.watchers.unsubscribe(&self.key, self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn resubscribe_before_unwatch() {
+        let (watchers, mut commands_rx) = Watchers::new();
+        let key = "key".to_string();
+
+        let (subscription, _rx) = watchers.subscribe(key.clone());
+        assert_matches!(commands_rx.try_recv(), Ok(WatcherCommand::Watch(x)) if x == key);
+        assert!(watchers.notify(&key, "value".into()));
+
+        // Resubscribe before connection processed UNWATCH
+        drop(subscription);
+        let (_subscription, mut rx) = watchers.subscribe(key.clone());
+        assert_eq!(rx.try_recv().ok(), Some(Value::from("value")));
+        assert!(watchers.is_watched(&key));
+
+        assert_matches!(commands_rx.try_recv(), Ok(WatcherCommand::Unwatch(x)) if x == key);
+        assert!(!watchers.take_unwatched(&key));
+        assert!(commands_rx.try_recv().is_err());
+        assert!(watchers.notify(&key, "new value".into()));
+        assert_eq!(rx.try_recv().ok(), Some(Value::from("new value")));
+    }
+
+    #[test]
+    fn unwatch() {
+        let (watchers, mut commands_rx) = Watchers::new();
+        let key = "key".to_string();
+
+        let (subscription, _rx) = watchers.subscribe(key.clone());
+        drop(subscription);
+        assert!(!watchers.is_watched(&key));
+        assert_matches!(commands_rx.try_recv(), Ok(WatcherCommand::Watch(_)));
+        assert_matches!(commands_rx.try_recv(), Ok(WatcherCommand::Unwatch(_)));
+        assert!(watchers.take_unwatched(&key));
+        assert!(!watchers.take_unwatched(&key));
+        assert!(watchers.keys().is_empty());
+    }
+}
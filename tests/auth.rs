@@ -0,0 +1,37 @@
+use std::sync::{Arc, Mutex};
+
+use tarantool_rs::{AuthMethod, Connection};
+use tracing_test::traced_test;
+
+use crate::common::{pap_sha256_handler, FakeServer};
+
+mod common;
+
+#[tokio::test]
+#[traced_test]
+async fn advertised_pap_sha256_without_tls() -> Result<(), anyhow::Error> {
+    let auth_methods = Arc::new(Mutex::new(Vec::new()));
+    let server = FakeServer::spawn(pap_sha256_handler(auth_methods.clone())).await;
+    let _conn = Connection::builder()
+        .auth("user", "secret")
+        .build(server.addr())
+        .await?;
+    assert_eq!(*auth_methods.lock().unwrap(), ["chap-sha1"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn explicit_pap_sha256_without_tls() -> Result<(), anyhow::Error> {
+    let auth_methods = Arc::new(Mutex::new(Vec::new()));
+    let server = FakeServer::spawn(pap_sha256_handler(auth_methods.clone())).await;
+    let _conn = Connection::builder()
+        .auth("user", "secret")
+        .auth_method(AuthMethod::PapSha256)
+        .build(server.addr())
+        .await?;
+    assert_eq!(*auth_methods.lock().unwrap(), ["pap-sha256"]);
+
+    Ok(())
+}
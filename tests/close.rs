@@ -0,0 +1,60 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use rmpv::Value;
+use tarantool_rs::{Connection, Executor};
+use tokio::sync::Notify;
+use tracing_test::traced_test;
+
+use crate::common::{answer_basic, request_type, FakeServer};
+
+mod common;
+
+#[tokio::test]
+#[traced_test]
+async fn close_with_committing_transaction() -> Result<(), anyhow::Error> {
+    let commit_received = Arc::new(Notify::new());
+    let rolled_back = Arc::new(Mutex::new(Vec::new()));
+    let handler = {
+        let commit_received = commit_received.clone();
+        let rolled_back = rolled_back.clone();
+        Arc::new(
+            move |request: common::Request, responder: &common::Responder| {
+                match request.request_type {
+                    request_type::BEGIN => responder.ok(request.sync, Value::Map(vec![])),
+                    // Commit takes a while, so connection is closed while it is in flight
+                    request_type::COMMIT => {
+                        commit_received.notify_one();
+                        let responder = responder.clone();
+                        tokio::spawn(async move {
+                            tokio::time::sleep(Duration::from_millis(100)).await;
+                            responder.ok(request.sync, Value::Map(vec![]));
+                        });
+                    }
+                    request_type::ROLLBACK => {
+                        rolled_back.lock().unwrap().push(request.stream_id);
+                        responder.ok(request.sync, Value::Map(vec![]));
+                    }
+                    _ => answer_basic(request, responder),
+                }
+            },
+        )
+    };
+    let server = FakeServer::spawn(handler).await;
+    let conn = Connection::builder().build(server.addr()).await?;
+
+    let committed = conn.transaction().await?;
+    let rolled_back_on_close = conn.transaction().await?;
+    let commit = tokio::spawn(committed.commit());
+    commit_received.notified().await;
+
+    conn.close(Duration::from_secs(1)).await?;
+    commit.await??;
+    // Only transaction, which was not finished, is rolled back
+    assert_eq!(rolled_back.lock().unwrap().len(), 1);
+    drop(rolled_back_on_close);
+
+    Ok(())
+}
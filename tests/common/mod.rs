@@ -1,6 +1,21 @@
+#![allow(dead_code)]
+
+use std::{
+    io::Cursor,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
 use async_trait::async_trait;
+use rmpv::Value;
 use tarantool_rs::Connection;
 use tarantool_test_container::testcontainers::ImageArgs;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::TcpListener,
+    sync::{mpsc, watch},
+    task::JoinHandle,
+};
 
 pub type TarantoolImage = tarantool_test_container::TarantoolImage<TarantoolArgs>;
 pub type TarantoolTestContainer = tarantool_test_container::TarantoolTestContainer<TarantoolArgs>;
@@ -36,3 +51,294 @@ impl TarantoolTestContainerExt for TarantoolTestContainer {
             .await
     }
 }
+
+/// IPROTO request types, used by fake server.
+pub mod request_type {
+    pub const AUTH: u64 = 7;
+    pub const EVAL: u64 = 8;
+    pub const EXECUTE: u64 = 11;
+    pub const PREPARE: u64 = 13;
+    pub const BEGIN: u64 = 14;
+    pub const COMMIT: u64 = 15;
+    pub const ROLLBACK: u64 = 16;
+    pub const PING: u64 = 64;
+    pub const ID: u64 = 73;
+    pub const WATCH: u64 = 74;
+    pub const UNWATCH: u64 = 75;
+}
+
+/// Request, received by fake server.
+#[derive(Debug)]
+pub struct Request {
+    pub request_type: u64,
+    pub sync: u64,
+    pub stream_id: Option<u64>,
+    pub body: Value,
+}
+
+impl Request {
+    /// Value of key in request body.
+    pub fn get(&self, key: u64) -> Option<&Value> {
+        map_get(&self.body, key)
+    }
+}
+
+/// Handle for sending responses and events to single client of fake server.
+#[derive(Clone)]
+pub struct Responder {
+    tx: mpsc::UnboundedSender<Option<Vec<u8>>>,
+}
+
+impl Responder {
+    /// Send successful response with body.
+    pub fn ok(&self, sync: u64, body: Value) {
+        self.send(0, sync, body);
+    }
+
+    /// Send successful response with `data` array in body (like responses to CALL or EVAL).
+    pub fn data(&self, sync: u64, data: Vec<Value>) {
+        self.ok(sync, Value::Map(vec![(0x30.into(), Value::Array(data))]));
+    }
+
+    /// Send error response.
+    pub fn error(&self, sync: u64, code: u32, message: &str) {
+        self.send(
+            0x8000 + code,
+            sync,
+            Value::Map(vec![(0x31.into(), message.into())]),
+        );
+    }
+
+    /// Send out-of-band push for request.
+    pub fn push(&self, sync: u64, value: Value) {
+        self.send(
+            0x80,
+            sync,
+            Value::Map(vec![(0x30.into(), Value::Array(vec![value]))]),
+        );
+    }
+
+    /// Send EVENT with new value of watched key.
+    pub fn event(&self, key: &str, value: Value) {
+        self.send(
+            76,
+            0,
+            Value::Map(vec![(0x57.into(), key.into()), (0x58.into(), value)]),
+        );
+    }
+
+    /// Close connection to client after all previously sent responses.
+    pub fn close(&self) {
+        let _ = self.tx.send(None);
+    }
+
+    fn send(&self, code: u32, sync: u64, body: Value) {
+        let header = Value::Map(vec![
+            (0.into(), code.into()),
+            (1.into(), sync.into()),
+            (5.into(), 1.into()),
+        ]);
+        let mut payload = Vec::new();
+        rmpv::encode::write_value(&mut payload, &header).unwrap();
+        rmpv::encode::write_value(&mut payload, &body).unwrap();
+        let mut frame = Vec::new();
+        rmp::encode::write_u32(&mut frame, payload.len() as u32).unwrap();
+        frame.extend(payload);
+        let _ = self.tx.send(Some(frame));
+    }
+}
+
+/// Handler of requests to fake server.
+pub type Handler = Arc<dyn Fn(Request, &Responder) + Send + Sync>;
+
+/// Answer requests, which are made by connection itself: ID, AUTH and PING.
+///
+/// ID response advertises protocol version 3 with streams, transactions,
+/// error extension and watchers.
+pub fn answer_basic(request: Request, responder: &Responder) {
+    match request.request_type {
+        request_type::ID => {
+            let features = Value::Array(vec![0.into(), 1.into(), 2.into(), 3.into()]);
+            responder.ok(
+                request.sync,
+                Value::Map(vec![(0x54.into(), 3.into()), (0x55.into(), features)]),
+            );
+        }
+        request_type::AUTH | request_type::PING => responder.ok(request.sync, Value::Map(vec![])),
+        _ => {}
+    }
+}
+
+/// Advertise `pap-sha256` auth method in ID response, recording methods of AUTH requests.
+pub fn pap_sha256_handler(auth_methods: Arc<Mutex<Vec<String>>>) -> Handler {
+    Arc::new(move |request, responder| match request.request_type {
+        request_type::ID => responder.ok(
+            request.sync,
+            Value::Map(vec![
+                (0x54.into(), 3.into()),
+                (0x5b.into(), "pap-sha256".into()),
+            ]),
+        ),
+        request_type::AUTH => {
+            let method = request
+                .get(0x21)
+                .and_then(|x| x.as_array()?.first()?.as_str())
+                .unwrap_or_default();
+            auth_methods.lock().unwrap().push(method.into());
+            responder.ok(request.sync, Value::Map(vec![]));
+        }
+        _ => answer_basic(request, responder),
+    })
+}
+
+/// Server, which pretends to be Tarantool, for tests which don't need real instance.
+pub struct FakeServer {
+    addr: SocketAddr,
+    clients: Arc<Mutex<Vec<Responder>>>,
+    accepted: watch::Receiver<usize>,
+    accept_task: JoinHandle<()>,
+}
+
+impl FakeServer {
+    /// Start server on random port.
+    pub async fn spawn(handler: Handler) -> Self {
+        Self::spawn_on("127.0.0.1:0".parse().unwrap(), handler).await
+    }
+
+    /// Start server on specified address (e.g. to restart stopped server).
+    pub async fn spawn_on(addr: SocketAddr, handler: Handler) -> Self {
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let clients: Arc<Mutex<Vec<Responder>>> = Arc::default();
+        let (accepted_tx, accepted) = watch::channel(0);
+
+        let task_clients = clients.clone();
+        let accept_task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let responder = serve(stream, handler.clone());
+                task_clients.lock().unwrap().push(responder);
+                accepted_tx.send_modify(|x| *x += 1);
+            }
+        });
+
+        Self {
+            addr,
+            clients,
+            accepted,
+            accept_task,
+        }
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Number of accepted connections.
+    pub fn accepted(&self) -> usize {
+        *self.accepted.borrow()
+    }
+
+    /// Wait until server accepts `count` connections in total.
+    pub async fn wait_accepted(&mut self, count: usize) {
+        self.accepted.wait_for(|x| *x >= count).await.unwrap();
+    }
+
+    /// Close all connections to clients, leaving server running.
+    pub fn drop_clients(&self) {
+        for client in self.clients.lock().unwrap().drain(..) {
+            client.close();
+        }
+    }
+
+    /// Stop accepting new connections and close existing ones.
+    ///
+    /// After this address of server can be reused with [`FakeServer::spawn_on`].
+    pub async fn stop(&mut self) {
+        self.accept_task.abort();
+        let _ = (&mut self.accept_task).await;
+        self.drop_clients();
+    }
+}
+
+impl Drop for FakeServer {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+        self.drop_clients();
+    }
+}
+
+/// Serve single client, connected with `stream`: send greeting
+/// and pass all requests to `handler`.
+pub fn serve<S>(stream: S, handler: Handler) -> Responder
+where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    let (mut reader, mut writer) = tokio::io::split(stream);
+    let (tx, mut rx) = mpsc::unbounded_channel::<Option<Vec<u8>>>();
+    let responder = Responder { tx };
+
+    tokio::spawn(async move {
+        if writer.write_all(&greeting()).await.is_err() {
+            return;
+        }
+        while let Some(Some(frame)) = rx.recv().await {
+            if writer.write_all(&frame).await.is_err() {
+                return;
+            }
+        }
+        let _ = writer.shutdown().await;
+    });
+
+    let reader_responder = responder.clone();
+    tokio::spawn(async move {
+        while let Some(request) = read_request(&mut reader).await {
+            handler(request, &reader_responder);
+        }
+    });
+
+    responder
+}
+
+fn greeting() -> Vec<u8> {
+    let mut line = b"Tarantool 2.11.1 (Binary) 8ad2b5a8-5b4b-4b65-9e0e-1b4fbb5b0d4b".to_vec();
+    line.resize(63, b' ');
+    line.push(b'\n');
+    let mut salt = b"QK2HoFZGXTXBq2vFj7soCsHqTo6PGTF575ssUBAJLAI=".to_vec();
+    salt.resize(63, b' ');
+    salt.push(b'\n');
+    line.extend(salt);
+    line
+}
+
+async fn read_request(stream: &mut (impl AsyncRead + Unpin)) -> Option<Request> {
+    let len: u64 = match stream.read_u8().await.ok()? {
+        x @ 0x00..=0x7f => x.into(),
+        0xcc => stream.read_u8().await.ok()?.into(),
+        0xcd => stream.read_u16().await.ok()?.into(),
+        0xce => stream.read_u32().await.ok()?.into(),
+        0xcf => stream.read_u64().await.ok()?,
+        _ => return None,
+    };
+    let mut buf = Cursor::new(vec![0; len as usize]);
+    stream.read_exact(buf.get_mut()).await.ok()?;
+    let header = rmpv::decode::read_value(&mut buf).ok()?;
+    let body = if buf.position() < len {
+        rmpv::decode::read_value(&mut buf).ok()?
+    } else {
+        Value::Nil
+    };
+    let get = |key| map_get(&header, key).and_then(Value::as_u64);
+    Some(Request {
+        request_type: get(0)?,
+        sync: get(1)?,
+        stream_id: get(0x0a),
+        body,
+    })
+}
+
+fn map_get(map: &Value, key: u64) -> Option<&Value> {
+    map.as_map()?
+        .iter()
+        .find(|(k, _)| k.as_u64() == Some(key))
+        .map(|(_, v)| v)
+}
@@ -0,0 +1,78 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use assert_matches::assert_matches;
+use tarantool_rs::{Connection, ConnectionState, Error, ExecutorExt, ReconnectInterval};
+use tokio::time::Instant;
+use tracing_test::traced_test;
+
+use crate::common::{answer_basic, request_type, FakeServer, Handler};
+
+mod common;
+
+/// Answer PING requests only when `answer_pings` set, counting them.
+fn ping_handler(answer_pings: Arc<AtomicBool>, pings: Arc<AtomicUsize>) -> Handler {
+    Arc::new(move |request, responder| {
+        if request.request_type == request_type::PING {
+            pings.fetch_add(1, Ordering::SeqCst);
+            if !answer_pings.load(Ordering::SeqCst) {
+                return;
+            }
+        }
+        answer_basic(request, responder)
+    })
+}
+
+#[tokio::test]
+#[traced_test]
+async fn heartbeat_timeout() -> Result<(), anyhow::Error> {
+    let answer_pings = Arc::new(AtomicBool::new(true));
+    let pings = Arc::new(AtomicUsize::new(0));
+    let server = FakeServer::spawn(ping_handler(answer_pings.clone(), pings.clone())).await;
+    let conn = Connection::builder()
+        .heartbeat_interval(Duration::from_millis(50))
+        .heartbeat_timeout(Duration::from_millis(100))
+        .reconnect_interval(ReconnectInterval::fixed(Duration::from_millis(10)))
+        .build(server.addr())
+        .await?;
+    let mut state_rx = conn.state_changes();
+
+    // Heartbeats are answered, so connection stays alive
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(pings.load(Ordering::SeqCst) > 0);
+    assert_matches!(conn.state(), ConnectionState::Connected { .. });
+    assert_eq!(server.accepted(), 1);
+
+    // Server stops answering, connection torn down after heartbeat timeout
+    answer_pings.store(false, Ordering::SeqCst);
+    let stopped_answering = Instant::now();
+    let state = tokio::time::timeout(
+        Duration::from_secs(1),
+        state_rx.wait_for(ConnectionState::is_connecting),
+    )
+    .await??
+    .clone();
+    assert!(stopped_answering.elapsed() >= Duration::from_millis(100));
+    assert_matches!(
+        state,
+        ConnectionState::Reconnecting { last_error, .. }
+            if matches!(&*last_error, Error::Io(err) if err.kind() == std::io::ErrorKind::TimedOut)
+    );
+
+    // And reconnects
+    answer_pings.store(true, Ordering::SeqCst);
+    tokio::time::timeout(
+        Duration::from_secs(1),
+        state_rx.wait_for(|x| matches!(x, ConnectionState::Connected { .. })),
+    )
+    .await??;
+    assert_eq!(server.accepted(), 2);
+    conn.ping().await?;
+
+    Ok(())
+}
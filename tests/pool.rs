@@ -0,0 +1,92 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use rmpv::Value;
+use tarantool_rs::{Connection, ExecutorExt, ReconnectInterval};
+use tracing_test::traced_test;
+
+use crate::common::{answer_basic, request_type, FakeServer, Handler};
+
+mod common;
+
+/// Answer PREPARE and EXECUTE requests, logging them along with `instance` id.
+fn sql_handler(instance: usize, log: Arc<Mutex<Vec<(u64, usize)>>>) -> Handler {
+    Arc::new(move |request, responder| match request.request_type {
+        request_type::PREPARE => {
+            log.lock().unwrap().push((request_type::PREPARE, instance));
+            responder.ok(request.sync, Value::Map(vec![(0x43.into(), 1.into())]));
+        }
+        request_type::EXECUTE => {
+            log.lock().unwrap().push((request_type::EXECUTE, instance));
+            responder.ok(request.sync, Value::Map(vec![]));
+        }
+        _ => answer_basic(request, responder),
+    })
+}
+
+/// Prepare statement and execute it several times through generic executor.
+async fn prepare_and_execute<E: ExecutorExt>(executor: &E) -> Result<(), anyhow::Error> {
+    let stmt = executor.prepare_sql("SELECT 1").await?;
+    for _ in 0..4 {
+        let _ = stmt.execute(()).await?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn prepared_statement_bound_to_connection() -> Result<(), anyhow::Error> {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let server_1 = FakeServer::spawn(sql_handler(1, log.clone())).await;
+    let server_2 = FakeServer::spawn(sql_handler(2, log.clone())).await;
+    let pool = Connection::builder()
+        .build_pool([server_1.addr(), server_2.addr()])
+        .await?;
+
+    prepare_and_execute(&pool).await?;
+
+    let log = log.lock().unwrap();
+    let (_, instance) = log[0];
+    assert_eq!(log[0].0, request_type::PREPARE);
+    assert_eq!(log.len(), 5);
+    assert!(log[1..]
+        .iter()
+        .all(|x| *x == (request_type::EXECUTE, instance)));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn unavailable_member_connected_in_background() -> Result<(), anyhow::Error> {
+    let server_1 = FakeServer::spawn(Arc::new(answer_basic)).await;
+    // Find address, on which nothing is listening
+    let addr_2 = FakeServer::spawn(Arc::new(answer_basic)).await.addr();
+    let pool = Connection::builder()
+        .reconnect_interval(ReconnectInterval::fixed(Duration::from_millis(10)))
+        .build_pool([server_1.addr(), addr_2])
+        .await?;
+    assert_eq!(pool.len(), 2);
+
+    let mut server_2 = FakeServer::spawn_on(addr_2, Arc::new(answer_basic)).await;
+    tokio::time::timeout(Duration::from_secs(1), server_2.wait_accepted(1)).await?;
+    for _ in 0..4 {
+        pool.ping().await?;
+    }
+    assert_eq!(server_1.accepted(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn all_members_unavailable() -> Result<(), anyhow::Error> {
+    let addr_1 = FakeServer::spawn(Arc::new(answer_basic)).await.addr();
+    let addr_2 = FakeServer::spawn(Arc::new(answer_basic)).await.addr();
+    let res = Connection::builder().build_pool([addr_1, addr_2]).await;
+    assert!(res.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,197 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use assert_matches::assert_matches;
+use tarantool_rs::{Connection, Error, ExecutorExt, ProxyConfig};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use tracing_test::traced_test;
+
+use crate::common::{answer_basic, FakeServer};
+
+mod common;
+
+const USERNAME: &str = "user";
+const PASSWORD: &str = "password";
+/// Base64 of `user:password`.
+const BASIC_TOKEN: &str = "dXNlcjpwYXNzd29yZA==";
+
+/// Start SOCKS5 proxy, which optionally requires username/password authentication.
+async fn spawn_socks5_proxy(require_auth: bool) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut header = [0; 2];
+                stream.read_exact(&mut header).await.unwrap();
+                let mut methods = vec![0; header[1].into()];
+                stream.read_exact(&mut methods).await.unwrap();
+                if !require_auth {
+                    stream.write_all(&[5, 0]).await.unwrap();
+                } else if methods.contains(&2) {
+                    stream.write_all(&[5, 2]).await.unwrap();
+                    let _version = stream.read_u8().await.unwrap();
+                    let username = read_string(&mut stream).await;
+                    let password = read_string(&mut stream).await;
+                    if (username.as_str(), password.as_str()) != (USERNAME, PASSWORD) {
+                        stream.write_all(&[1, 1]).await.unwrap();
+                        return;
+                    }
+                    stream.write_all(&[1, 0]).await.unwrap();
+                } else {
+                    stream.write_all(&[5, 0xff]).await.unwrap();
+                    return;
+                }
+
+                let mut request = [0; 4];
+                stream.read_exact(&mut request).await.unwrap();
+                assert_eq!(request[..3], [5, 1, 0]);
+                let host = match request[3] {
+                    1 => {
+                        let mut ip = [0; 4];
+                        stream.read_exact(&mut ip).await.unwrap();
+                        std::net::Ipv4Addr::from(ip).to_string()
+                    }
+                    3 => read_string(&mut stream).await,
+                    x => panic!("Unexpected address type {x}"),
+                };
+                let port = stream.read_u16().await.unwrap();
+                let Ok(mut target) = TcpStream::connect((host.as_str(), port)).await else {
+                    stream
+                        .write_all(&[5, 5, 0, 1, 0, 0, 0, 0, 0, 0])
+                        .await
+                        .unwrap();
+                    return;
+                };
+                stream
+                    .write_all(&[5, 0, 0, 1, 127, 0, 0, 1, 0, 0])
+                    .await
+                    .unwrap();
+                let _ = tokio::io::copy_bidirectional(&mut stream, &mut target).await;
+            });
+        }
+    });
+
+    addr
+}
+
+async fn read_string(stream: &mut (impl AsyncRead + Unpin)) -> String {
+    let mut buf = vec![0; stream.read_u8().await.unwrap().into()];
+    stream.read_exact(&mut buf).await.unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+/// Start HTTP proxy, which supports `CONNECT` method and requires Basic authentication.
+async fn spawn_http_proxy() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut request = Vec::new();
+                while !request.ends_with(b"\r\n\r\n") {
+                    request.push(stream.read_u8().await.unwrap());
+                }
+                let request = String::from_utf8(request).unwrap();
+                let target = request
+                    .strip_prefix("CONNECT ")
+                    .and_then(|x| x.split_whitespace().next())
+                    .unwrap()
+                    .to_owned();
+                let authorization = format!("Proxy-Authorization: Basic {BASIC_TOKEN}\r\n");
+                if !request.contains(&authorization) {
+                    stream
+                        .write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n")
+                        .await
+                        .unwrap();
+                    return;
+                }
+                let mut target = TcpStream::connect(target).await.unwrap();
+                stream
+                    .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+                    .await
+                    .unwrap();
+                let _ = tokio::io::copy_bidirectional(&mut stream, &mut target).await;
+            });
+        }
+    });
+
+    addr
+}
+
+#[tokio::test]
+#[traced_test]
+async fn socks5_proxy() -> Result<(), anyhow::Error> {
+    let server = FakeServer::spawn(Arc::new(answer_basic)).await;
+    let port = server.addr().port();
+    let proxy_addr = spawn_socks5_proxy(false).await;
+
+    let conn = Connection::builder()
+        .proxy(ProxyConfig::socks5(proxy_addr.to_string()))
+        .build(format!("localhost:{}", port))
+        .await?;
+    conn.ping().await?;
+    assert_eq!(conn.peer_addr(), Some(proxy_addr));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn socks5_proxy_auth() -> Result<(), anyhow::Error> {
+    let server = FakeServer::spawn(Arc::new(answer_basic)).await;
+    let port = server.addr().port();
+    let proxy_addr = spawn_socks5_proxy(true).await;
+
+    let mut proxy = ProxyConfig::socks5(proxy_addr.to_string());
+    proxy.credentials(USERNAME, PASSWORD);
+    let conn = Connection::builder()
+        .proxy(proxy)
+        .build(format!("127.0.0.1:{}", port))
+        .await?;
+    conn.ping().await?;
+
+    proxy = ProxyConfig::socks5(proxy_addr.to_string());
+    proxy.credentials(USERNAME, "wrong");
+    let res = Connection::builder()
+        .proxy(proxy)
+        .build(format!("127.0.0.1:{}", port))
+        .await;
+    assert_matches!(res, Err(Error::Io(_)));
+
+    let res = Connection::builder()
+        .proxy(ProxyConfig::socks5(proxy_addr.to_string()))
+        .build(format!("127.0.0.1:{}", port))
+        .await;
+    assert_matches!(res, Err(Error::Io(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn http_connect_proxy() -> Result<(), anyhow::Error> {
+    let server = FakeServer::spawn(Arc::new(answer_basic)).await;
+    let port = server.addr().port();
+    let proxy_addr = spawn_http_proxy().await;
+
+    let mut proxy = ProxyConfig::http_connect(proxy_addr.to_string());
+    proxy.credentials(USERNAME, PASSWORD);
+    let conn = Connection::builder()
+        .proxy(proxy)
+        .build(format!("localhost:{}", port))
+        .await?;
+    conn.ping().await?;
+
+    let res = Connection::builder()
+        .proxy(ProxyConfig::http_connect(proxy_addr.to_string()))
+        .build(format!("localhost:{}", port))
+        .await;
+    assert_matches!(res, Err(Error::Io(_)));
+
+    Ok(())
+}
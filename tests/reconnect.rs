@@ -0,0 +1,278 @@
+use std::{
+    io,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use assert_matches::assert_matches;
+use tarantool_rs::{
+    Connection, ConnectionState, Error, ExecutorExt, ReconnectInterval, RequestPolicy, Resolver,
+};
+use tokio::{
+    net::TcpListener,
+    sync::{watch, Semaphore},
+    time::Instant,
+};
+use tracing_test::traced_test;
+
+use crate::common::{answer_basic, FakeServer};
+
+mod common;
+
+/// Resolver, which waits for permit before each resolution and fails
+/// when address is `None`, so connection attempts are controlled by test.
+fn gated_resolver(permits: Arc<Semaphore>, addr: Arc<Mutex<Option<SocketAddr>>>) -> impl Resolver {
+    move |_: &str| {
+        let permits = permits.clone();
+        let addr = addr.clone();
+        async move {
+            permits.acquire().await.unwrap().forget();
+            match *addr.lock().unwrap() {
+                Some(addr) => Ok(vec![addr]),
+                None => Err(io::Error::new(io::ErrorKind::NotFound, "Server is down")),
+            }
+        }
+    }
+}
+
+async fn wait_attempt(state_rx: &mut watch::Receiver<ConnectionState>, attempt: u32) {
+    let wait = state_rx.wait_for(
+        |x| matches!(x, ConnectionState::Reconnecting { attempt: x, .. } if *x == attempt),
+    );
+    tokio::time::timeout(Duration::from_secs(1), wait)
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+async fn wait_closed(state_rx: &mut watch::Receiver<ConnectionState>) {
+    let wait = state_rx.wait_for(|x| matches!(x, ConnectionState::Closed));
+    tokio::time::timeout(Duration::from_secs(1), wait)
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+#[tokio::test]
+#[traced_test]
+async fn reconnect_attempts_numbering() -> Result<(), anyhow::Error> {
+    let server = FakeServer::spawn(Arc::new(answer_basic)).await;
+    let permits = Arc::new(Semaphore::new(1));
+    let addr = Arc::new(Mutex::new(Some(server.addr())));
+    let conn = Connection::builder()
+        .resolver(gated_resolver(permits.clone(), addr.clone()))
+        .reconnect_interval(ReconnectInterval::fixed(Duration::ZERO))
+        .max_reconnect_attempts(3)
+        .build("tarantool")
+        .await?;
+    let mut state_rx = conn.state_changes();
+
+    *addr.lock().unwrap() = None;
+    server.drop_clients();
+    for attempt in 1..=3 {
+        wait_attempt(&mut state_rx, attempt).await;
+        permits.add_permits(1);
+    }
+    wait_closed(&mut state_rx).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn lazy_connect_attempts_numbering() -> Result<(), anyhow::Error> {
+    let permits = Arc::new(Semaphore::new(0));
+    let conn = Connection::builder()
+        .resolver(gated_resolver(permits.clone(), Arc::default()))
+        .reconnect_interval(ReconnectInterval::fixed(Duration::ZERO))
+        .max_reconnect_attempts(3)
+        .build_lazy("tarantool")?;
+    let mut state_rx = conn.state_changes();
+
+    // First attempt is reported as `Connecting`
+    assert!(matches!(conn.state(), ConnectionState::Connecting));
+    permits.add_permits(1);
+    for attempt in 2..=3 {
+        wait_attempt(&mut state_rx, attempt).await;
+        permits.add_permits(1);
+    }
+    wait_closed(&mut state_rx).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn max_reconnect_attempts() -> Result<(), anyhow::Error> {
+    let mut server = FakeServer::spawn(Arc::new(answer_basic)).await;
+    let conn = Connection::builder()
+        .reconnect_interval(ReconnectInterval::fixed(Duration::from_millis(10)))
+        .max_reconnect_attempts(2)
+        .build(server.addr())
+        .await?;
+    let mut state_rx = conn.state_changes();
+
+    server.stop().await;
+    wait_attempt(&mut state_rx, 1).await;
+    assert_matches!(
+        conn.ping().await,
+        Err(Error::ReconnectFailed { attempts: 2 })
+    );
+    wait_closed(&mut state_rx).await;
+    assert_matches!(conn.ping().await, Err(_));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn max_reconnect_duration() -> Result<(), anyhow::Error> {
+    let mut server = FakeServer::spawn(Arc::new(answer_basic)).await;
+    let conn = Connection::builder()
+        .reconnect_interval(ReconnectInterval::fixed(Duration::from_millis(10)))
+        .max_reconnect_duration(Duration::from_millis(100))
+        .build(server.addr())
+        .await?;
+    let mut state_rx = conn.state_changes();
+
+    let started = Instant::now();
+    server.stop().await;
+    wait_attempt(&mut state_rx, 1).await;
+    assert_matches!(conn.ping().await, Err(Error::ReconnectFailed { .. }));
+    assert!(started.elapsed() >= Duration::from_millis(100));
+    wait_closed(&mut state_rx).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn fail_fast_policy() -> Result<(), anyhow::Error> {
+    let mut server = FakeServer::spawn(Arc::new(answer_basic)).await;
+    let addr = server.addr();
+    let conn = Connection::builder()
+        .reconnect_interval(ReconnectInterval::fixed(Duration::from_millis(10)))
+        .request_policy(RequestPolicy::FailFast)
+        .build(addr)
+        .await?;
+    let mut state_rx = conn.state_changes();
+
+    server.stop().await;
+    wait_attempt(&mut state_rx, 1).await;
+    assert_matches!(
+        tokio::time::timeout(Duration::from_millis(100), conn.ping()).await,
+        Ok(Err(Error::NotConnected))
+    );
+
+    // Requests are sent again after reconnect
+    let _server = FakeServer::spawn_on(addr, Arc::new(answer_basic)).await;
+    let _ = state_rx
+        .wait_for(|x| matches!(x, ConnectionState::Connected { .. }))
+        .await?;
+    conn.ping().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn queue_with_deadline_policy_expired() -> Result<(), anyhow::Error> {
+    let mut server = FakeServer::spawn(Arc::new(answer_basic)).await;
+    let conn = Connection::builder()
+        .reconnect_interval(ReconnectInterval::fixed(Duration::from_millis(10)))
+        .request_policy(RequestPolicy::QueueWithDeadline(Duration::from_millis(100)))
+        .build(server.addr())
+        .await?;
+    let mut state_rx = conn.state_changes();
+
+    server.stop().await;
+    wait_attempt(&mut state_rx, 1).await;
+    let started = Instant::now();
+    assert_matches!(conn.ping().await, Err(Error::NotConnected));
+    assert!(started.elapsed() >= Duration::from_millis(100));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn queue_with_deadline_policy_reconnected() -> Result<(), anyhow::Error> {
+    let mut server = FakeServer::spawn(Arc::new(answer_basic)).await;
+    let addr = server.addr();
+    let conn = Connection::builder()
+        .reconnect_interval(ReconnectInterval::fixed(Duration::from_millis(10)))
+        .request_policy(RequestPolicy::QueueWithDeadline(Duration::from_secs(1)))
+        .build(addr)
+        .await?;
+    let mut state_rx = conn.state_changes();
+
+    server.stop().await;
+    wait_attempt(&mut state_rx, 1).await;
+    let ping = tokio::spawn({
+        let conn = conn.clone();
+        async move { conn.ping().await }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let _server = FakeServer::spawn_on(addr, Arc::new(answer_basic)).await;
+    ping.await??;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn queue_policy() -> Result<(), anyhow::Error> {
+    let mut server = FakeServer::spawn(Arc::new(answer_basic)).await;
+    let addr = server.addr();
+    let conn = Connection::builder()
+        .reconnect_interval(ReconnectInterval::fixed(Duration::from_millis(10)))
+        .request_policy(RequestPolicy::Queue)
+        .build(addr)
+        .await?;
+    let mut state_rx = conn.state_changes();
+
+    server.stop().await;
+    wait_attempt(&mut state_rx, 1).await;
+    let ping = tokio::spawn({
+        let conn = conn.clone();
+        async move { conn.ping().await }
+    });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(!ping.is_finished());
+    let _server = FakeServer::spawn_on(addr, Arc::new(answer_basic)).await;
+    ping.await??;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn max_reconnect_duration_with_hanging_connect() -> Result<(), anyhow::Error> {
+    let mut server = FakeServer::spawn(Arc::new(answer_basic)).await;
+    let addr = server.addr();
+    let conn = Connection::builder()
+        .reconnect_interval(ReconnectInterval::fixed(Duration::from_millis(10)))
+        .max_reconnect_duration(Duration::from_millis(200))
+        .build(addr)
+        .await?;
+    let mut state_rx = conn.state_changes();
+
+    // Server accepts connections, but never sends greeting
+    server.stop().await;
+    let listener = TcpListener::bind(addr).await?;
+    let _accept_task = tokio::spawn(async move {
+        let mut sockets = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            sockets.push(socket);
+        }
+    });
+    wait_attempt(&mut state_rx, 1).await;
+    let started = Instant::now();
+    let res = tokio::time::timeout(Duration::from_secs(2), conn.ping()).await?;
+    assert_matches!(res, Err(Error::ReconnectFailed { .. }));
+    assert!(started.elapsed() < Duration::from_secs(1));
+    wait_closed(&mut state_rx).await;
+
+    Ok(())
+}
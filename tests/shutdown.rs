@@ -0,0 +1,68 @@
+use std::{sync::Arc, time::Duration};
+
+use tarantool_rs::{Connection, ExecutorExt, ReconnectInterval};
+use tokio::sync::mpsc;
+use tracing_test::traced_test;
+
+use crate::common::{answer_basic, request_type, FakeServer, Handler, Responder};
+
+mod common;
+
+/// Don't answer EVAL requests, passing them to test instead.
+fn eval_handler(evals_tx: mpsc::UnboundedSender<(u64, Responder)>) -> Handler {
+    Arc::new(move |request, responder| match request.request_type {
+        request_type::EVAL => {
+            let _ = evals_tx.send((request.sync, responder.clone()));
+        }
+        _ => answer_basic(request, responder),
+    })
+}
+
+#[tokio::test]
+#[traced_test]
+async fn graceful_shutdown() -> Result<(), anyhow::Error> {
+    let (evals_tx, mut evals_rx) = mpsc::unbounded_channel();
+    let mut server = FakeServer::spawn(eval_handler(evals_tx)).await;
+    let conn = Connection::builder()
+        .reconnect_interval(ReconnectInterval::fixed(Duration::from_millis(10)))
+        .build(server.addr())
+        .await?;
+
+    let spawn_eval = || {
+        let conn = conn.clone();
+        tokio::spawn(async move { conn.eval("return", ()).await })
+    };
+    let in_flight = [spawn_eval(), spawn_eval()];
+    let mut received = Vec::new();
+    for _ in 0..in_flight.len() {
+        received.push(evals_rx.recv().await.unwrap());
+    }
+
+    // Server notifies about shutdown with requests in flight
+    let responder = received[0].1.clone();
+    responder.event("box.shutdown", true.into());
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // New request is not sent to shutting down server, but queued
+    let queued = spawn_eval();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(evals_rx.try_recv().is_err());
+    assert!(!queued.is_finished());
+    assert_eq!(server.accepted(), 1);
+
+    // In-flight requests are finished
+    for (sync, responder) in received {
+        responder.data(sync, vec![]);
+    }
+    for x in in_flight {
+        x.await??;
+    }
+
+    // Connection is reestablished and queued request sent through it
+    server.wait_accepted(2).await;
+    let (sync, responder) = evals_rx.recv().await.unwrap();
+    responder.data(sync, vec![]);
+    queued.await??;
+
+    Ok(())
+}
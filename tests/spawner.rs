@@ -0,0 +1,49 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use futures::future::BoxFuture;
+use tarantool_rs::{Connection, ConnectionState, ExecutorExt, ReconnectInterval};
+use tracing_test::traced_test;
+
+use crate::common::{answer_basic, FakeServer};
+
+mod common;
+
+#[tokio::test]
+#[traced_test]
+async fn custom_spawner() -> Result<(), anyhow::Error> {
+    let server = FakeServer::spawn(Arc::new(answer_basic)).await;
+    let spawned = Arc::new(AtomicUsize::new(0));
+    let conn = Connection::builder()
+        .reconnect_interval(ReconnectInterval::fixed(Duration::from_millis(10)))
+        .spawner({
+            let spawned = spawned.clone();
+            move |future: BoxFuture<'static, ()>| {
+                spawned.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(future);
+            }
+        })
+        .build(server.addr())
+        .await?;
+    let mut state_rx = conn.state_changes();
+
+    // Dispatcher and writer of connection
+    conn.ping().await?;
+    assert_eq!(spawned.load(Ordering::SeqCst), 2);
+
+    // Writer of new connection
+    server.drop_clients();
+    state_rx.wait_for(ConnectionState::is_connecting).await?;
+    state_rx
+        .wait_for(|x| matches!(x, ConnectionState::Connected { .. }))
+        .await?;
+    conn.ping().await?;
+    assert_eq!(spawned.load(Ordering::SeqCst), 3);
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use assert_matches::assert_matches;
+use tarantool_rs::{Connection, Error, ExecutorExt, ReconnectInterval};
+use tokio::sync::Notify;
+use tracing_test::traced_test;
+
+use crate::common::{answer_basic, request_type, FakeServer, Handler};
+
+mod common;
+
+/// Never answer EVAL requests, counting and reporting them.
+fn hanging_eval_handler(evals: Arc<AtomicUsize>, received: Arc<Notify>) -> Handler {
+    Arc::new(move |request, responder| match request.request_type {
+        request_type::EVAL => {
+            evals.fetch_add(1, Ordering::SeqCst);
+            received.notify_one();
+        }
+        _ => answer_basic(request, responder),
+    })
+}
+
+#[tokio::test]
+#[traced_test]
+async fn timeout_after_send() -> Result<(), anyhow::Error> {
+    let handler = hanging_eval_handler(Arc::default(), Arc::default());
+    let server = FakeServer::spawn(handler).await;
+    let conn = Connection::builder()
+        .timeout(Duration::from_millis(100))
+        .build(server.addr())
+        .await?;
+
+    assert_matches!(
+        conn.eval("return", ()).await,
+        Err(Error::Timeout { sent: true })
+    );
+    // Timed out request is forgotten, so close doesn't wait for it
+    conn.close(Duration::from_millis(100)).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn timeout_before_send() -> Result<(), anyhow::Error> {
+    // Find address, on which nothing is listening
+    let addr = FakeServer::spawn(Arc::new(answer_basic)).await.addr();
+
+    let conn = Connection::builder()
+        .timeout(Duration::from_millis(100))
+        .reconnect_interval(ReconnectInterval::fixed(Duration::from_millis(10)))
+        .build_lazy(addr)?;
+    assert_matches!(
+        conn.eval("return", ()).await,
+        Err(Error::Timeout { sent: false })
+    );
+
+    // Timed out request is not sent after connection is established
+    let evals = Arc::new(AtomicUsize::new(0));
+    let _server =
+        FakeServer::spawn_on(addr, hanging_eval_handler(evals.clone(), Arc::default())).await;
+    conn.ping().await?;
+    assert_eq!(evals.load(Ordering::SeqCst), 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn dropped_request_is_abandoned() -> Result<(), anyhow::Error> {
+    let received = Arc::new(Notify::new());
+    let server = FakeServer::spawn(hanging_eval_handler(Arc::default(), received.clone())).await;
+    let conn = Connection::builder().build(server.addr()).await?;
+
+    let request = tokio::spawn({
+        let conn = conn.clone();
+        async move { conn.eval("return", ()).await }
+    });
+    received.notified().await;
+    request.abort();
+    assert!(request.await.unwrap_err().is_cancelled());
+
+    // Connection doesn't wait for response to dropped request
+    conn.close(Duration::from_millis(100)).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn connect_timeout() -> Result<(), anyhow::Error> {
+    // Never finish handshake
+    let server = FakeServer::spawn(Arc::new(|_, _| {})).await;
+
+    let res = tokio::time::timeout(
+        Duration::from_secs(1),
+        Connection::builder()
+            .timeout(Duration::from_secs(5))
+            .connect_timeout(Duration::from_millis(100))
+            .build(server.addr()),
+    )
+    .await?;
+    assert_matches!(res, Err(Error::ConnectTimeout));
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "tls")]
+
+use std::sync::{Arc, Mutex};
+
+use assert_matches::assert_matches;
+use rcgen::{BasicConstraints, Certificate, CertificateParams, IsCa, KeyPair};
+use tarantool_rs::{Connection, Error, ExecutorExt, TlsConfig};
+use tokio::net::TcpListener;
+use tokio_rustls::{
+    rustls::{pki_types::PrivateKeyDer, ServerConfig},
+    TlsAcceptor,
+};
+use tracing_test::traced_test;
+
+use crate::common::{answer_basic, pap_sha256_handler, Handler};
+
+mod common;
+
+struct Pki {
+    ca_cert: Certificate,
+    server_cert: Certificate,
+    server_key: KeyPair,
+}
+
+fn generate_pki() -> Pki {
+    let ca_key = KeyPair::generate().unwrap();
+    let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    let ca_cert = ca_params.self_signed(&ca_key).unwrap();
+
+    let server_key = KeyPair::generate().unwrap();
+    let server_cert = CertificateParams::new(vec!["localhost".into()])
+        .unwrap()
+        .signed_by(&server_key, &ca_cert, &ca_key)
+        .unwrap();
+
+    Pki {
+        ca_cert,
+        server_cert,
+        server_key,
+    }
+}
+
+/// Start TLS server, which pretends to be Tarantool and answers with `handler`.
+async fn spawn_server(pki: &Pki, handler: Handler) -> u16 {
+    let config = ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(
+            vec![pki.server_cert.der().clone()],
+            PrivateKeyDer::Pkcs8(pki.server_key.serialize_der().into()),
+        )
+        .unwrap();
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let acceptor = acceptor.clone();
+            let handler = handler.clone();
+            tokio::spawn(async move {
+                if let Ok(stream) = acceptor.accept(stream).await {
+                    common::serve(stream, handler);
+                }
+            });
+        }
+    });
+
+    port
+}
+
+#[tokio::test]
+#[traced_test]
+async fn tls_connection() -> Result<(), anyhow::Error> {
+    let pki = generate_pki();
+    let port = spawn_server(&pki, Arc::new(answer_basic)).await;
+
+    let mut tls = TlsConfig::new();
+    tls.ca_certificates_pem(pki.ca_cert.pem());
+    let conn = Connection::builder()
+        .tls(tls)
+        .build(format!("localhost:{}", port))
+        .await?;
+    conn.ping().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn tls_unknown_ca() -> Result<(), anyhow::Error> {
+    let pki = generate_pki();
+    let port = spawn_server(&pki, Arc::new(answer_basic)).await;
+
+    let mut tls = TlsConfig::new();
+    tls.ca_certificates_pem(generate_pki().ca_cert.pem());
+    let res = Connection::builder()
+        .tls(tls)
+        .build(format!("localhost:{}", port))
+        .await;
+    assert_matches!(res, Err(Error::Io(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn tls_without_ca() -> Result<(), anyhow::Error> {
+    let res = Connection::builder()
+        .tls(TlsConfig::new())
+        .build("localhost:3301")
+        .await;
+    assert_matches!(res, Err(Error::Other(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn tls_advertised_pap_sha256() -> Result<(), anyhow::Error> {
+    let pki = generate_pki();
+    let auth_methods = Arc::new(Mutex::new(Vec::new()));
+    let port = spawn_server(&pki, pap_sha256_handler(auth_methods.clone())).await;
+
+    let mut tls = TlsConfig::new();
+    tls.ca_certificates_pem(pki.ca_cert.pem());
+    let _conn = Connection::builder()
+        .tls(tls)
+        .auth("user", "secret")
+        .build(format!("localhost:{}", port))
+        .await?;
+    assert_eq!(*auth_methods.lock().unwrap(), ["pap-sha256"]);
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures::StreamExt;
+use rmpv::Value;
+use tarantool_rs::{Connection, ExecutorExt};
+use tracing_test::traced_test;
+
+use crate::common::{answer_basic, request_type, FakeServer, Handler};
+
+mod common;
+
+/// Answer WATCH like Tarantool: first WATCH of key is answered with its value,
+/// next ones are acknowledgements and answered only when value changes.
+fn watch_handler(watched: Arc<Mutex<HashSet<String>>>) -> Handler {
+    Arc::new(move |request, responder| match request.request_type {
+        request_type::WATCH => {
+            let key = request.get(0x57).unwrap().as_str().unwrap().to_owned();
+            if watched.lock().unwrap().insert(key.clone()) && key == "status" {
+                responder.event(&key, "green".into());
+            }
+        }
+        request_type::UNWATCH => {
+            let key = request.get(0x57).unwrap().as_str().unwrap();
+            watched.lock().unwrap().remove(key);
+        }
+        _ => answer_basic(request, responder),
+    })
+}
+
+#[tokio::test]
+#[traced_test]
+async fn resubscribe_before_unwatch() -> Result<(), anyhow::Error> {
+    let watched = Arc::default();
+    let server = FakeServer::spawn(watch_handler(Arc::clone(&watched))).await;
+    let conn = Connection::builder().build(server.addr()).await?;
+
+    let mut watcher = conn.watch("status")?;
+    assert_eq!(watcher.next().await, Some(Value::from("green")));
+
+    // Subscribe again before connection sent UNWATCH
+    drop(watcher);
+    let mut watcher = conn.watch("status")?;
+    let value = tokio::time::timeout(Duration::from_secs(1), watcher.next()).await?;
+    assert_eq!(value, Some(Value::from("green")));
+
+    // Key is still watched on server
+    conn.ping().await?;
+    assert!(watched.lock().unwrap().contains("status"));
+
+    Ok(())
+}
+
+#[tokio::test]
+#[traced_test]
+async fn unwatch() -> Result<(), anyhow::Error> {
+    let watched = Arc::default();
+    let server = FakeServer::spawn(watch_handler(Arc::clone(&watched))).await;
+    let conn = Connection::builder().build(server.addr()).await?;
+
+    let mut watcher = conn.watch("status")?;
+    assert_eq!(watcher.next().await, Some(Value::from("green")));
+    drop(watcher);
+    conn.ping().await?;
+    assert!(!watched.lock().unwrap().contains("status"));
+
+    Ok(())
+}